@@ -0,0 +1,35 @@
+use std::io::{self, Read, Write};
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::Serialize;
+
+use crate::error::MktDataError;
+
+// A bundle is the compact json of a document, gzipped. It's what gets published,
+// the pretty json stays for diffs and debugging. Reading a bundle goes through the
+// same migrations as the json.
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+pub(crate) fn to_bundle<T: Serialize>(value: &T) -> Result<Vec<u8>, MktDataError> {
+    let json = serde_json::to_vec(value).map_err(|source| MktDataError::Serialize { source })?;
+    let mut encoder = GzEncoder::new(vec![], Compression::best());
+    encoder
+        .write_all(&json)
+        .and_then(|_| encoder.finish())
+        .map_err(|source| MktDataError::Bundle { path: None, source })
+}
+
+// plain json is accepted too, some servers decompress the bundle on the way
+pub(crate) fn bundle_to_json(bundle: &[u8]) -> Result<String, MktDataError> {
+    let json = if bundle.starts_with(&GZIP_MAGIC) {
+        let mut json = String::new();
+        GzDecoder::new(bundle)
+            .read_to_string(&mut json)
+            .map(|_| json)
+    } else {
+        String::from_utf8(bundle.to_vec())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    };
+    json.map_err(|source| MktDataError::Bundle { path: None, source })
+}
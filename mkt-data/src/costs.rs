@@ -0,0 +1,167 @@
+use std::{
+    iter::Sum,
+    ops::{Add, AddAssign},
+};
+
+use itertools::Itertools;
+
+use crate::{planner::CoveragePlan, Item, ItemId, ItemLvl, MktData, MktInventory, OwnedItem};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Cost {
+    // copies of the item, the first one included for an item to get
+    pub duplicates: u32,
+    pub level_tickets: u32,
+    pub cap_tickets: u32,
+    pub grand_stars: u32,
+}
+
+impl Add for Cost {
+    type Output = Cost;
+
+    fn add(mut self, rhs: Cost) -> Cost {
+        self += rhs;
+        self
+    }
+}
+
+impl AddAssign for Cost {
+    fn add_assign(&mut self, rhs: Cost) {
+        self.duplicates += rhs.duplicates;
+        self.level_tickets += rhs.level_tickets;
+        self.cap_tickets += rhs.cap_tickets;
+        self.grand_stars += rhs.grand_stars;
+    }
+}
+
+impl Sum for Cost {
+    fn sum<I: Iterator<Item = Cost>>(iter: I) -> Cost {
+        iter.fold(Cost::default(), Add::add)
+    }
+}
+
+// Duplicates of each level step from the current level, or from level 1 with the
+// first copy for an item to get. None when the data has no costs for the item.
+fn level_steps(
+    data: &MktData,
+    item: &Item,
+    lvl: Option<ItemLvl>,
+    target: ItemLvl,
+) -> Option<(u32, Vec<u32>)> {
+    let table = &data.rules.costs(item)?.level_duplicates;
+    let from = lvl.unwrap_or(1).max(1) as usize;
+    let to = target as usize;
+    let steps = if to > from {
+        table.get(from - 1..to - 1)?.to_vec()
+    } else {
+        vec![]
+    };
+    Some((if lvl.is_none() { 1 } else { 0 }, steps))
+}
+
+pub fn level_cost(
+    data: &MktData,
+    item: &Item,
+    lvl: Option<ItemLvl>,
+    target: ItemLvl,
+) -> Option<Cost> {
+    level_cost_using_tickets(data, item, lvl, target, 0)
+}
+
+// A level-up ticket replaces all the duplicates of a level, they go to the most
+// expensive levels first. The first copy of an item can't be replaced.
+pub fn level_cost_using_tickets(
+    data: &MktData,
+    item: &Item,
+    lvl: Option<ItemLvl>,
+    target: ItemLvl,
+    tickets: u32,
+) -> Option<Cost> {
+    let (first_copy, steps) = level_steps(data, item, lvl, target)?;
+    let used = (tickets as usize).min(steps.len());
+    let duplicates = steps.into_iter().sorted().rev().skip(used).sum::<u32>();
+    Some(Cost {
+        duplicates: first_copy + duplicates,
+        level_tickets: used as u32,
+        ..Cost::default()
+    })
+}
+
+// the cap tiers come from the item ladder, none past its last tier
+pub fn cap_cost(data: &MktData, item: &Item, owned: &OwnedItem, target_tier: u8) -> Option<Cost> {
+    let ladder = data.point_ladder(item)?;
+    let costs = data.rules.costs(item)?;
+    // the base points are not a tier, like in cap_tier
+    let tiers = ladder.caps.len().saturating_sub(2);
+    let (from, to) = (owned.point_cap_tier(ladder) as usize, target_tier as usize);
+    if to > tiers {
+        return None;
+    }
+    let range = from.min(to)..to;
+    Some(Cost {
+        cap_tickets: costs.cap_tickets.get(range.clone())?.iter().sum(),
+        grand_stars: range
+            .map(|i| costs.cap_grand_stars.get(i).copied().unwrap_or(0))
+            .sum(),
+        ..Cost::default()
+    })
+}
+
+// none for items not in the data, or without costs
+pub fn upgrade_cost(data: &MktData, inv: &MktInventory, upgrade: &Upgrade) -> Option<Cost> {
+    let item = data.get_item(&upgrade.id)?;
+    let owned = inv.get_item(&upgrade.id);
+    let mut cost = Cost::default();
+    if let Some(lvl) = upgrade.lvl {
+        cost += level_cost(data, item, owned.map(|o| o.lvl), lvl)?;
+    }
+    // an item to get starts without points
+    if let Some(tier) = upgrade.cap_tier {
+        let new_item;
+        let owned = match owned {
+            Some(owned) => owned,
+            None => {
+                new_item = OwnedItem::new(item.id.clone(), 1, 0);
+                &new_item
+            }
+        };
+        cost += cap_cost(data, item, owned, tier)?;
+    }
+    Some(cost)
+}
+
+// the total, and the upgrades left out because their cost is unknown
+pub fn plan_cost(data: &MktData, inv: &MktInventory, upgrades: &[Upgrade]) -> (Cost, Vec<ItemId>) {
+    let mut unknown = vec![];
+    let mut total = Cost::default();
+    for upgrade in upgrades {
+        match upgrade_cost(data, inv, upgrade) {
+            Some(cost) => total += cost,
+            None => unknown.push(upgrade.id.clone()),
+        }
+    }
+    (total, unknown)
+}
+
+// level ups of a coverage plan, new items are counted from their first copy
+pub fn coverage_plan_cost(data: &MktData, plan: &CoveragePlan) -> (Cost, Vec<ItemId>) {
+    let mut unknown = vec![];
+    let mut total = Cost::default();
+    for p in &plan.items {
+        let cost = data
+            .get_item(&p.id)
+            .and_then(|item| level_cost(data, item, p.owned_lvl, p.lvl));
+        match cost {
+            Some(cost) => total += cost,
+            None => unknown.push(p.id.clone()),
+        }
+    }
+    (total, unknown)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Upgrade {
+    pub id: ItemId,
+    pub lvl: Option<ItemLvl>,
+    pub cap_tier: Option<u8>,
+}
@@ -0,0 +1,347 @@
+use std::collections::{BTreeMap, HashMap};
+
+use itertools::Itertools;
+
+use crate::{
+    course_generation_from_id, item_type_from_id, Course, CourseGeneration, CourseId, Item, ItemId,
+    ItemLvl, ItemType, MktData, MktInventory, Rarity,
+};
+
+// How well a course is covered for an item type, from the worst to the best.
+// A favored item is only a fallback, it doesn't get the favorite bonus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CoverageLevel {
+    Uncovered,
+    Favored,
+    Favorite,
+}
+
+impl Course {
+    pub fn coverage_level(&self, inv: &MktInventory, i_type: ItemType) -> CoverageLevel {
+        let owned = inv.items(i_type);
+        self.coverage_level_by(i_type, |id| owned.get(id).map(|i| i.lvl).unwrap_or(0))
+    }
+
+    // for callers keeping the owned levels elsewhere, 0 when not owned
+    pub fn coverage_level_by(
+        &self,
+        i_type: ItemType,
+        owned_lvl: impl Fn(&str) -> ItemLvl,
+    ) -> CoverageLevel {
+        let of_type = |id: &str| item_type_from_id(id) == Some(i_type);
+        if self
+            .favorite_items
+            .iter()
+            .any(|r| of_type(&r.id) && owned_lvl(&r.id) >= r.lvl)
+        {
+            CoverageLevel::Favorite
+        } else if self
+            .favored_items
+            .iter()
+            .any(|r| of_type(&r.id) && owned_lvl(&r.id) > 0)
+        {
+            CoverageLevel::Favored
+        } else {
+            CoverageLevel::Uncovered
+        }
+    }
+
+    // the worst level of the three item types
+    pub fn overall_coverage_level(&self, inv: &MktInventory) -> CoverageLevel {
+        [ItemType::Driver, ItemType::Kart, ItemType::Glider]
+            .iter()
+            .map(|i_type| self.coverage_level(inv, *i_type))
+            .min()
+            .unwrap_or(CoverageLevel::Uncovered)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CourseStats {
+    // favorite items of each type, and how many are owned at the required level
+    pub driver_count: usize,
+    pub kart_count: usize,
+    pub glider_count: usize,
+    pub driver_owned_count: usize,
+    pub kart_owned_count: usize,
+    pub glider_owned_count: usize,
+    pub driver_coverage: CoverageLevel,
+    pub kart_coverage: CoverageLevel,
+    pub glider_coverage: CoverageLevel,
+}
+
+impl CourseStats {
+    pub fn new(course: &Course, inv: &MktInventory) -> Self {
+        let mut stats = CourseStats {
+            driver_count: 0,
+            kart_count: 0,
+            glider_count: 0,
+            driver_owned_count: 0,
+            kart_owned_count: 0,
+            glider_owned_count: 0,
+            driver_coverage: course.coverage_level(inv, ItemType::Driver),
+            kart_coverage: course.coverage_level(inv, ItemType::Kart),
+            glider_coverage: course.coverage_level(inv, ItemType::Glider),
+        };
+        for r in &course.favorite_items {
+            let (count, owned_count) = match item_type_from_id(&r.id) {
+                Some(ItemType::Driver) => (&mut stats.driver_count, &mut stats.driver_owned_count),
+                Some(ItemType::Kart) => (&mut stats.kart_count, &mut stats.kart_owned_count),
+                Some(ItemType::Glider) => (&mut stats.glider_count, &mut stats.glider_owned_count),
+                None => continue,
+            };
+            *count += 1;
+            if owned_lvl(inv, &r.id) >= r.lvl {
+                *owned_count += 1;
+            }
+        }
+        stats
+    }
+
+    // none for all the item types together
+    pub fn count(&self, i_type: Option<ItemType>) -> usize {
+        match i_type {
+            None => self.driver_count + self.kart_count + self.glider_count,
+            Some(ItemType::Driver) => self.driver_count,
+            Some(ItemType::Kart) => self.kart_count,
+            Some(ItemType::Glider) => self.glider_count,
+        }
+    }
+
+    pub fn owned_count(&self, i_type: Option<ItemType>) -> usize {
+        match i_type {
+            None => self.driver_owned_count + self.kart_owned_count + self.glider_owned_count,
+            Some(ItemType::Driver) => self.driver_owned_count,
+            Some(ItemType::Kart) => self.kart_owned_count,
+            Some(ItemType::Glider) => self.glider_owned_count,
+        }
+    }
+
+    pub fn coverage(&self, i_type: Option<ItemType>) -> CoverageLevel {
+        match i_type {
+            None => self
+                .driver_coverage
+                .min(self.kart_coverage)
+                .min(self.glider_coverage),
+            Some(ItemType::Driver) => self.driver_coverage,
+            Some(ItemType::Kart) => self.kart_coverage,
+            Some(ItemType::Glider) => self.glider_coverage,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemStats {
+    // favorite courses reached at the owned level, out of all of them
+    pub fav_course_count: usize,
+    pub max_fav_course_count: usize,
+    // uncovered courses the item would cover when added, then when fully leveled
+    pub add_course_count: usize,
+    pub max_add_course_count: usize,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CourseGroupStats {
+    pub course_count: usize,
+    // covered by favorites for all the item types
+    pub covered_count: usize,
+    // covered, but with a favored item for at least one type
+    pub favored_count: usize,
+}
+
+impl CourseGroupStats {
+    fn add(&mut self, coverage: CoverageLevel) {
+        self.course_count += 1;
+        match coverage {
+            CoverageLevel::Favorite => self.covered_count += 1,
+            CoverageLevel::Favored => self.favored_count += 1,
+            CoverageLevel::Uncovered => {}
+        }
+    }
+
+    fn remove(&mut self, coverage: CoverageLevel) {
+        self.course_count -= 1;
+        match coverage {
+            CoverageLevel::Favorite => self.covered_count -= 1,
+            CoverageLevel::Favored => self.favored_count -= 1,
+            CoverageLevel::Uncovered => {}
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ItemGroupStats {
+    pub item_count: usize,
+    pub owned_count: usize,
+}
+
+// what CoverageReport::update_item recomputed
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CoverageUpdate {
+    pub courses: Vec<CourseId>,
+    pub items: Vec<ItemId>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CoverageReport {
+    pub courses: HashMap<CourseId, CourseStats>,
+    pub items: HashMap<ItemId, ItemStats>,
+    pub generations: BTreeMap<CourseGeneration, CourseGroupStats>,
+    pub rarities: BTreeMap<(ItemType, Rarity), ItemGroupStats>,
+}
+
+impl CoverageReport {
+    pub fn new(data: &MktData, inv: &MktInventory) -> Self {
+        let mut report = CoverageReport::default();
+
+        for course in data.courses.values() {
+            let stats = CourseStats::new(course, inv);
+            report
+                .generations
+                .entry(course_generation_from_id(&course.id))
+                .or_default()
+                .add(stats.coverage(None));
+            report.courses.insert(course.id.clone(), stats);
+        }
+
+        for item in data
+            .drivers
+            .values()
+            .chain(data.karts.values())
+            .chain(data.gliders.values())
+        {
+            let lvl = owned_lvl(inv, &item.id);
+            report
+                .items
+                .insert(item.id.clone(), report.item_stats(item, lvl));
+
+            let group = report
+                .rarities
+                .entry((item.i_type, item.rarity))
+                .or_default();
+            group.item_count += 1;
+            if lvl > 0 {
+                group.owned_count += 1;
+            }
+        }
+
+        report
+    }
+
+    // Recomputes only what a change of one owned item can touch: the courses of the
+    // item, and the items sharing those courses. Much faster than a full report when
+    // editing the inventory one item at a time.
+    pub fn update_item(&mut self, data: &MktData, inv: &MktInventory, id: &str) -> CoverageUpdate {
+        let mut update = CoverageUpdate::default();
+        let item = match data.get_item(id) {
+            Some(item) => item,
+            None => return update,
+        };
+
+        // courses
+        for c in item.favorite_courses.iter().chain(&item.favored_courses) {
+            let course = match data.courses.get(&c.id) {
+                Some(course) => course,
+                None => continue,
+            };
+            let stats = CourseStats::new(course, inv);
+            let group = self
+                .generations
+                .entry(course_generation_from_id(&course.id))
+                .or_default();
+            if let Some(old) = self.courses.get(&course.id) {
+                group.remove(old.coverage(None));
+            }
+            group.add(stats.coverage(None));
+            self.courses.insert(course.id.clone(), stats);
+            update.courses.push(course.id.clone());
+        }
+
+        // items, the changed one included
+        let courses = update.courses.iter().filter_map(|id| data.courses.get(id));
+        let ids = courses
+            .flat_map(|c| c.favorite_items.iter().map(|r| &r.id))
+            .chain(Some(&item.id))
+            .unique()
+            .cloned()
+            .collect_vec();
+        for id in ids {
+            if let Some(item) = data.get_item(&id) {
+                let stats = self.item_stats(item, owned_lvl(inv, &id));
+                self.items.insert(id.clone(), stats);
+                update.items.push(id);
+            }
+        }
+
+        // the owned count of the item rarity
+        let group = self.rarities.entry((item.i_type, item.rarity)).or_default();
+        group.owned_count = data
+            .items(item.i_type)
+            .values()
+            .filter(|i| i.rarity == item.rarity && owned_lvl(inv, &i.id) > 0)
+            .count();
+
+        update
+    }
+
+    fn item_stats(&self, item: &Item, lvl: ItemLvl) -> ItemStats {
+        let mut stats = ItemStats {
+            fav_course_count: 0,
+            max_fav_course_count: 0,
+            add_course_count: 0,
+            max_add_course_count: 0,
+        };
+        for r in &item.favorite_courses {
+            // only the courses without an owned favorite of that type count
+            let add = self
+                .courses
+                .get(&r.id)
+                .map(|s| s.owned_count(Some(item.i_type)))
+                .filter(|c| *c > 0)
+                .map(|_| 0)
+                .unwrap_or(1);
+            if lvl == 0 {
+                if r.lvl == 1 {
+                    stats.add_course_count += add;
+                }
+                stats.max_add_course_count += add;
+            } else if lvl >= r.lvl {
+                stats.fav_course_count += 1;
+            } else {
+                stats.max_add_course_count += add;
+            }
+            stats.max_fav_course_count += 1;
+        }
+        stats
+    }
+
+    // all the generations together
+    pub fn course_totals(&self) -> CourseGroupStats {
+        let mut totals = CourseGroupStats::default();
+        for g in self.generations.values() {
+            totals.course_count += g.course_count;
+            totals.covered_count += g.covered_count;
+            totals.favored_count += g.favored_count;
+        }
+        totals
+    }
+
+    // all the rarities of an item type together
+    pub fn item_totals(&self, i_type: ItemType) -> ItemGroupStats {
+        let mut totals = ItemGroupStats::default();
+        for g in self
+            .rarities
+            .iter()
+            .filter(|((t, _), _)| *t == i_type)
+            .map(|(_, g)| g)
+        {
+            totals.item_count += g.item_count;
+            totals.owned_count += g.owned_count;
+        }
+        totals
+    }
+}
+
+fn owned_lvl(inv: &MktInventory, id: &str) -> ItemLvl {
+    inv.get_item(id).map(|i| i.lvl).unwrap_or(0)
+}
@@ -0,0 +1,374 @@
+use std::{collections::HashSet, fmt::Write};
+
+use itertools::Itertools;
+
+use crate::{
+    Course, CourseId, ItemId, ItemLvl, ItemPoints, ItemRequirement, ItemType, MktData,
+    MktInventory, OwnedItem, Rarity,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ItemState {
+    pub lvl: ItemLvl,
+    pub points: ItemPoints,
+}
+
+impl From<&OwnedItem> for ItemState {
+    fn from(item: &OwnedItem) -> Self {
+        ItemState {
+            lvl: item.lvl,
+            points: item.points,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemChange {
+    pub id: ItemId,
+    pub i_type: ItemType,
+    // none when the item was added
+    pub old: Option<ItemState>,
+    // none when the item was removed
+    pub new: Option<ItemState>,
+}
+
+impl ItemChange {
+    pub fn is_added(&self) -> bool {
+        self.old.is_none()
+    }
+
+    pub fn is_removed(&self) -> bool {
+        self.new.is_none()
+    }
+
+    pub fn lvl_change(&self) -> Option<(ItemLvl, ItemLvl)> {
+        let (old, new) = (self.old?.lvl, self.new?.lvl);
+        Some((old, new)).filter(|_| old != new)
+    }
+
+    pub fn points_change(&self) -> Option<(ItemPoints, ItemPoints)> {
+        let (old, new) = (self.old?.points, self.new?.points);
+        Some((old, new)).filter(|_| old != new)
+    }
+
+    // needs the data, the tiers depend on the item point ladder
+    pub fn cap_tier_change(&self, data: &MktData) -> Option<(u8, u8)> {
+        let ladder = data.point_ladder(data.get_item(&self.id)?)?;
+        let tier = |s: ItemState| ladder.cap_tier(s.points);
+        let (old, new) = (tier(self.old?), tier(self.new?));
+        Some((old, new)).filter(|_| old != new)
+    }
+
+    fn describe(&self, data: &MktData) -> String {
+        let mut text = String::new();
+        match (self.old, self.new) {
+            (None, Some(new)) | (Some(new), None) => {
+                write!(text, "lvl {}, {} pts", new.lvl, new.points).unwrap();
+            }
+            _ => {
+                let mut changes = vec![];
+                if let Some((old, new)) = self.lvl_change() {
+                    changes.push(format!("lvl {} -> {}", old, new));
+                }
+                if let Some((old, new)) = self.points_change() {
+                    changes.push(format!("{} -> {} pts", old, new));
+                }
+                if let Some((old, new)) = self.cap_tier_change(data) {
+                    changes.push(format!("cap tier {} -> {}", old, new));
+                }
+                text.push_str(&changes.join(", "));
+            }
+        }
+        text
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct InventoryDiff {
+    // sorted by item type and id
+    pub items: Vec<ItemChange>,
+}
+
+impl InventoryDiff {
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn added(&self) -> impl Iterator<Item = &ItemChange> {
+        self.items.iter().filter(|i| i.is_added())
+    }
+
+    pub fn removed(&self) -> impl Iterator<Item = &ItemChange> {
+        self.items.iter().filter(|i| i.is_removed())
+    }
+
+    pub fn changed(&self) -> impl Iterator<Item = &ItemChange> {
+        self.items
+            .iter()
+            .filter(|i| !i.is_added() && !i.is_removed())
+    }
+
+    // one line per item, + added, - removed, ~ changed
+    pub fn to_text(&self, data: &MktData) -> String {
+        let mut text = String::new();
+        for (i_type, items) in &self.items.iter().group_by(|i| i.i_type) {
+            writeln!(text, "{}s", capitalize(&i_type.to_string())).unwrap();
+            for item in items {
+                let sign = if item.is_added() {
+                    '+'
+                } else if item.is_removed() {
+                    '-'
+                } else {
+                    '~'
+                };
+                writeln!(
+                    text,
+                    "{} {}: {}",
+                    sign,
+                    item_name(data, &item.id),
+                    item.describe(data)
+                )
+                .unwrap();
+            }
+        }
+        text
+    }
+
+    pub fn to_markdown(&self, data: &MktData) -> String {
+        let mut text = String::new();
+        for (i_type, items) in &self.items.iter().group_by(|i| i.i_type) {
+            writeln!(text, "### {}s\n", capitalize(&i_type.to_string())).unwrap();
+            writeln!(text, "| Item | Change | Details |").unwrap();
+            writeln!(text, "| --- | --- | --- |").unwrap();
+            for item in items {
+                let change = if item.is_added() {
+                    "added"
+                } else if item.is_removed() {
+                    "removed"
+                } else {
+                    "changed"
+                };
+                writeln!(
+                    text,
+                    "| {} | {} | {} |",
+                    item_name(data, &item.id),
+                    change,
+                    item.describe(data)
+                )
+                .unwrap();
+            }
+            writeln!(text).unwrap();
+        }
+        text
+    }
+}
+
+fn item_name<'a>(data: &'a MktData, id: &'a str) -> &'a str {
+    data.get_item(id).map(|i| i.name.as_str()).unwrap_or(id)
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    chars
+        .next()
+        .map(|c| c.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+impl MktInventory {
+    // changes to go from this inventory to the other one, dates are ignored
+    pub fn diff(&self, other: &MktInventory) -> InventoryDiff {
+        let mut items = vec![];
+        for i_type in [ItemType::Driver, ItemType::Kart, ItemType::Glider] {
+            let (old, new) = (self.items(i_type), other.items(i_type));
+            for id in old.keys().chain(new.keys()).unique().sorted() {
+                let change = ItemChange {
+                    id: id.clone(),
+                    i_type,
+                    old: old.get(id).map(ItemState::from),
+                    new: new.get(id).map(ItemState::from),
+                };
+                if change.old != change.new {
+                    items.push(change);
+                }
+            }
+        }
+        InventoryDiff { items }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortChange {
+    // course or item id
+    pub id: String,
+    pub old: Option<u32>,
+    pub new: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CoverageChange {
+    pub course: CourseId,
+    // favorites, and favored items at level 0
+    pub added: Vec<ItemRequirement>,
+    pub removed: Vec<ItemRequirement>,
+}
+
+// what an update changes in the data, everything sorted by id
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DataDiff {
+    pub added_items: Vec<ItemId>,
+    pub removed_items: Vec<ItemId>,
+    pub rarity_changes: Vec<(ItemId, Rarity, Rarity)>,
+    pub added_courses: Vec<CourseId>,
+    pub removed_courses: Vec<CourseId>,
+    pub sort_changes: Vec<SortChange>,
+    pub coverage_changes: Vec<CoverageChange>,
+}
+
+impl DataDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added_items.is_empty()
+            && self.removed_items.is_empty()
+            && self.rarity_changes.is_empty()
+            && self.added_courses.is_empty()
+            && self.removed_courses.is_empty()
+            && self.sort_changes.is_empty()
+            && self.coverage_changes.is_empty()
+    }
+
+    // removed things are named from the old data, everything else from the new one
+    pub fn to_markdown(&self, old: &MktData, new: &MktData) -> String {
+        let mut text = String::new();
+        let name = |id: &str| {
+            let data = if new.get_item(id).is_some() || new.courses.contains_key(id) {
+                new
+            } else {
+                old
+            };
+            data.courses
+                .get(id)
+                .map(|c| c.name.as_str())
+                .unwrap_or_else(|| item_name(data, id))
+                .to_string()
+        };
+        let mut section = |title: &str, lines: Vec<String>| {
+            if !lines.is_empty() {
+                writeln!(text, "### {}\n", title).unwrap();
+                for line in lines {
+                    writeln!(text, "- {}", line).unwrap();
+                }
+                writeln!(text).unwrap();
+            }
+        };
+
+        let item = |id: &ItemId| format!("{} (`{}`)", name(id), id);
+        section("Added items", self.added_items.iter().map(item).collect());
+        section(
+            "Removed items",
+            self.removed_items.iter().map(item).collect(),
+        );
+        section(
+            "Rarity changes",
+            self.rarity_changes
+                .iter()
+                .map(|(id, old, new)| format!("{}: {:?} -> {:?}", name(id), old, new))
+                .collect(),
+        );
+        section(
+            "Added courses",
+            self.added_courses.iter().map(item).collect(),
+        );
+        section(
+            "Removed courses",
+            self.removed_courses.iter().map(item).collect(),
+        );
+        let sort = |s: Option<u32>| s.map(|s| s.to_string()).unwrap_or_else(|| "none".into());
+        section(
+            "Sort changes",
+            self.sort_changes
+                .iter()
+                .map(|c| format!("{}: {} -> {}", name(&c.id), sort(c.old), sort(c.new)))
+                .collect(),
+        );
+
+        let requirement = |sign: char, r: &ItemRequirement| match r.lvl {
+            0 => format!("{} {} (favored)", sign, name(&r.id)),
+            lvl => format!("{} {} (lvl {})", sign, name(&r.id), lvl),
+        };
+        for c in &self.coverage_changes {
+            section(
+                &format!("Coverage of {}", name(&c.course)),
+                c.added
+                    .iter()
+                    .map(|r| requirement('+', r))
+                    .chain(c.removed.iter().map(|r| requirement('-', r)))
+                    .collect(),
+            );
+        }
+        text
+    }
+}
+
+impl MktData {
+    // changes to go from this data to the other one
+    pub fn diff(&self, other: &MktData) -> DataDiff {
+        let mut diff = DataDiff::default();
+
+        for i_type in [ItemType::Driver, ItemType::Kart, ItemType::Glider] {
+            let (old, new) = (self.items(i_type), other.items(i_type));
+            for id in old.keys().chain(new.keys()).unique().sorted() {
+                match (old.get(id), new.get(id)) {
+                    (None, Some(_)) => diff.added_items.push(id.clone()),
+                    (Some(_), None) => diff.removed_items.push(id.clone()),
+                    (Some(o), Some(n)) => {
+                        if o.rarity != n.rarity {
+                            diff.rarity_changes.push((id.clone(), o.rarity, n.rarity));
+                        }
+                        if o.sort != n.sort {
+                            diff.sort_changes.push(SortChange {
+                                id: id.clone(),
+                                old: o.sort,
+                                new: n.sort,
+                            });
+                        }
+                    }
+                    (None, None) => {}
+                }
+            }
+        }
+
+        let (old, new) = (&self.courses, &other.courses);
+        for id in old.keys().chain(new.keys()).unique().sorted() {
+            let (o, n) = (old.get(id), new.get(id));
+            match (o, n) {
+                (None, Some(_)) => diff.added_courses.push(id.clone()),
+                (Some(_), None) => diff.removed_courses.push(id.clone()),
+                (Some(o), Some(n)) if o.sort != n.sort => diff.sort_changes.push(SortChange {
+                    id: id.clone(),
+                    old: o.sort,
+                    new: n.sort,
+                }),
+                _ => {}
+            }
+
+            // a level change is a removal and an addition
+            let requirements = |c: Option<&Course>| {
+                c.into_iter()
+                    .flat_map(|c| c.favorite_items.iter().chain(&c.favored_items))
+                    .cloned()
+                    .collect::<HashSet<_>>()
+            };
+            let (o, n) = (requirements(o), requirements(n));
+            let change = CoverageChange {
+                course: id.clone(),
+                added: n.difference(&o).cloned().sorted().collect(),
+                removed: o.difference(&n).cloned().sorted().collect(),
+            };
+            if !change.added.is_empty() || !change.removed.is_empty() {
+                diff.coverage_changes.push(change);
+            }
+        }
+
+        diff
+    }
+}
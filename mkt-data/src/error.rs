@@ -0,0 +1,181 @@
+use std::{error::Error, fmt::Display, fs, io};
+
+#[derive(Debug)]
+pub enum MktDataError {
+    // the file can't be read or written
+    Io {
+        path: String,
+        source: io::Error,
+    },
+    // the document is not valid json
+    Json {
+        path: Option<String>,
+        line: usize,
+        column: usize,
+        source: serde_json::Error,
+    },
+    // the document is valid json, but doesn't match the expected layout
+    Schema {
+        path: Option<String>,
+        // json path of the offending value, like drivers.d_mario.lvl
+        at: String,
+        // course or item id the offending value belongs to
+        id: Option<String>,
+        source: serde_json::Error,
+    },
+    // the document was written by a newer version
+    UnsupportedVersion {
+        path: Option<String>,
+        version: u64,
+        supported: u32,
+    },
+    // the bundle can't be compressed or decompressed
+    Bundle {
+        path: Option<String>,
+        source: io::Error,
+    },
+    // the value can't be written as json
+    Serialize {
+        source: serde_json::Error,
+    },
+}
+
+impl MktDataError {
+    pub fn is_not_found(&self) -> bool {
+        matches!(self, MktDataError::Io { source, .. } if source.kind() == io::ErrorKind::NotFound)
+    }
+
+    pub fn path(&self) -> Option<&str> {
+        match self {
+            MktDataError::Io { path, .. } => Some(path),
+            MktDataError::Json { path, .. }
+            | MktDataError::Schema { path, .. }
+            | MktDataError::UnsupportedVersion { path, .. }
+            | MktDataError::Bundle { path, .. } => path.as_deref(),
+            MktDataError::Serialize { .. } => None,
+        }
+    }
+
+    fn with_path(mut self, file_name: &str) -> Self {
+        match &mut self {
+            MktDataError::Json { path, .. }
+            | MktDataError::Schema { path, .. }
+            | MktDataError::UnsupportedVersion { path, .. }
+            | MktDataError::Bundle { path, .. } => *path = Some(file_name.into()),
+            MktDataError::Io { .. } | MktDataError::Serialize { .. } => {}
+        }
+        self
+    }
+}
+
+impl Display for MktDataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let path = self.path().unwrap_or("json");
+        match self {
+            MktDataError::Io { source, .. } => write!(f, "{}: {}", path, source),
+            MktDataError::Json {
+                line,
+                column,
+                source,
+                ..
+            } => write!(f, "{}:{}:{}: {}", path, line, column, source),
+            MktDataError::Schema { at, id, source, .. } => {
+                write!(f, "{}: invalid value at {}", path, at)?;
+                if let Some(id) = id {
+                    write!(f, " (id {})", id)?;
+                }
+                write!(f, ": {}", source)
+            }
+            MktDataError::UnsupportedVersion {
+                version, supported, ..
+            } => write!(
+                f,
+                "{}: version {} is newer than the supported version {}",
+                path, version, supported
+            ),
+            MktDataError::Bundle { source, .. } => {
+                write!(f, "{}: invalid bundle: {}", path, source)
+            }
+            MktDataError::Serialize { source } => write!(f, "can't write json: {}", source),
+        }
+    }
+}
+
+impl Error for MktDataError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MktDataError::Io { source, .. } | MktDataError::Bundle { source, .. } => Some(source),
+            MktDataError::Json { source, .. }
+            | MktDataError::Schema { source, .. }
+            | MktDataError::Serialize { source } => Some(source),
+            MktDataError::UnsupportedVersion { .. } => None,
+        }
+    }
+}
+
+impl From<serde_json::Error> for MktDataError {
+    fn from(source: serde_json::Error) -> Self {
+        MktDataError::Json {
+            path: None,
+            line: source.line(),
+            column: source.column(),
+            source,
+        }
+    }
+}
+
+impl From<serde_path_to_error::Error<serde_json::Error>> for MktDataError {
+    fn from(error: serde_path_to_error::Error<serde_json::Error>) -> Self {
+        let at = error.path().to_string();
+        // documents are maps of id to value, the id is the second segment (drivers.d_mario)
+        let id = error
+            .path()
+            .iter()
+            .filter_map(|s| match s {
+                serde_path_to_error::Segment::Map { key } => Some(key.clone()),
+                _ => None,
+            })
+            .nth(1);
+        MktDataError::Schema {
+            path: None,
+            at,
+            id,
+            source: error.into_inner(),
+        }
+    }
+}
+
+pub(crate) fn read_json(file_name: &str) -> Result<String, MktDataError> {
+    fs::read_to_string(file_name).map_err(|source| MktDataError::Io {
+        path: file_name.into(),
+        source,
+    })
+}
+
+pub(crate) fn write_json(file_name: &str, json: String) -> Result<(), MktDataError> {
+    fs::write(file_name, json).map_err(|source| MktDataError::Io {
+        path: file_name.into(),
+        source,
+    })
+}
+
+pub(crate) fn read_bundle(file_name: &str) -> Result<Vec<u8>, MktDataError> {
+    fs::read(file_name).map_err(|source| MktDataError::Io {
+        path: file_name.into(),
+        source,
+    })
+}
+
+pub(crate) fn write_bundle(file_name: &str, bundle: Vec<u8>) -> Result<(), MktDataError> {
+    fs::write(file_name, bundle).map_err(|source| MktDataError::Io {
+        path: file_name.into(),
+        source,
+    })
+}
+
+pub(crate) fn with_path<T>(
+    result: Result<T, MktDataError>,
+    file_name: &str,
+) -> Result<T, MktDataError> {
+    result.map_err(|e| e.with_path(file_name))
+}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::*, item_type_from_id, migration::*, planner::covered_courses, ItemId, ItemLvl,
+    ItemPoints, ItemType, MktData, MktInventory, OwnedItem,
+};
+
+// the state of an owned item from that date, level 0 when it was removed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryChange {
+    pub date: DateTime<Utc>,
+    pub id: ItemId,
+    pub lvl: ItemLvl,
+    pub points: ItemPoints,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Progress {
+    pub date: DateTime<Utc>,
+    pub owned_drivers: usize,
+    pub owned_karts: usize,
+    pub owned_gliders: usize,
+    // courses with an owned favorite item of that type, at the required level
+    pub covered_drivers: usize,
+    pub covered_karts: usize,
+    pub covered_gliders: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MktInventoryHistory {
+    #[serde(default)]
+    pub version: u32,
+    // sorted by date, only the changed items are kept, at most one per item and day
+    pub changes: Vec<HistoryChange>,
+}
+
+impl Default for MktInventoryHistory {
+    fn default() -> Self {
+        MktInventoryHistory {
+            version: MKT_HISTORY_VERSION,
+            changes: vec![],
+        }
+    }
+}
+
+impl MktInventoryHistory {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn from_json(json: &str) -> Result<MktInventoryHistory, MktDataError> {
+        migrate_history(json)
+    }
+
+    pub fn to_json(&self) -> Result<String, MktDataError> {
+        serde_json::to_string(self).map_err(|source| MktDataError::Serialize { source })
+    }
+
+    pub fn load(file_name: &str) -> Result<MktInventoryHistory, MktDataError> {
+        let json = read_json(file_name)?;
+        with_path(MktInventoryHistory::from_json(&json), file_name)
+    }
+
+    pub fn save(&self, file_name: &str) -> Result<(), MktDataError> {
+        write_json(file_name, self.to_json()?)
+    }
+
+    // Records the items changed since the inventory at that date. A change on the same
+    // day as the last change of the item replaces it. Returns false when nothing changed.
+    pub fn record(&mut self, inv: &MktInventory, date: DateTime<Utc>) -> bool {
+        let previous = self.at(date).unwrap_or_default();
+        // the first change after that date, its inventory must stay the same
+        let next_date = self.changes.iter().map(|c| c.date).find(|d| *d > date);
+
+        let mut recorded = false;
+        for i_type in [ItemType::Driver, ItemType::Kart, ItemType::Glider] {
+            let (old_items, new_items) = (previous.items(i_type), inv.items(i_type));
+            let mut ids: Vec<_> = old_items.keys().chain(new_items.keys()).collect();
+            ids.sort();
+            ids.dedup();
+            for id in ids {
+                let state = |items: &HashMap<ItemId, OwnedItem>| {
+                    items.get(id).map(|i| (i.lvl, i.points)).unwrap_or((0, 0))
+                };
+                let (old, new) = (state(old_items), state(new_items));
+                if old == new {
+                    continue;
+                }
+                // recorded out of order, the item is back to its old state after
+                if let Some(next_date) = next_date {
+                    if !self
+                        .changes
+                        .iter()
+                        .any(|c| c.date == next_date && &c.id == id)
+                    {
+                        let (lvl, points) = old;
+                        let index = self.changes.partition_point(|c| c.date <= next_date);
+                        let change = HistoryChange {
+                            date: next_date,
+                            id: id.clone(),
+                            lvl,
+                            points,
+                        };
+                        self.changes.insert(index, change);
+                    }
+                }
+                let (lvl, points) = new;
+                self.insert_change(HistoryChange {
+                    date,
+                    id: id.clone(),
+                    lvl,
+                    points,
+                });
+                recorded = true;
+            }
+        }
+        recorded
+    }
+
+    fn insert_change(&mut self, change: HistoryChange) {
+        // the item has a change later that day, or already at that date
+        let last = self.changes.iter().rposition(|c| c.id == change.id);
+        if let Some(last) = last {
+            let last_date = self.changes[last].date;
+            if last_date == change.date
+                || (last_date < change.date && last_date.date_naive() == change.date.date_naive())
+            {
+                self.changes.remove(last);
+                // back to the state before that change, like an undo
+                let before = self.item_at(&change.id, change.date);
+                if before == (change.lvl, change.points) {
+                    return;
+                }
+            }
+        }
+        let index = self.changes.partition_point(|c| c.date <= change.date);
+        self.changes.insert(index, change);
+    }
+
+    fn item_at(&self, id: &str, date: DateTime<Utc>) -> (ItemLvl, ItemPoints) {
+        self.changes
+            .iter()
+            .take_while(|c| c.date <= date)
+            .filter(|c| c.id == id)
+            .last()
+            .map(|c| (c.lvl, c.points))
+            .unwrap_or((0, 0))
+    }
+
+    // the inventory as it was at that date, none before the first change
+    pub fn at(&self, date: DateTime<Utc>) -> Option<MktInventory> {
+        if self.changes.first()?.date > date {
+            return None;
+        }
+        let mut inv = MktInventory::new();
+        for change in self.changes.iter().take_while(|c| c.date <= date) {
+            apply(&mut inv, change);
+        }
+        Some(inv)
+    }
+
+    pub fn progress_at(&self, data: &MktData, date: DateTime<Utc>) -> Option<Progress> {
+        self.at(date).map(|inv| progress(data, &inv, date))
+    }
+
+    // one point per date with changes, for charts
+    pub fn progress(&self, data: &MktData) -> Vec<Progress> {
+        let mut inv = MktInventory::new();
+        let mut points = vec![];
+        for (i, change) in self.changes.iter().enumerate() {
+            apply(&mut inv, change);
+            if self.changes.get(i + 1).map(|c| c.date) != Some(change.date) {
+                points.push(progress(data, &inv, change.date));
+            }
+        }
+        points
+    }
+
+    // the first date each level of the item was reached
+    pub fn level_timeline(&self, id: &str) -> Vec<(DateTime<Utc>, ItemLvl)> {
+        let mut timeline: Vec<(DateTime<Utc>, ItemLvl)> = vec![];
+        for c in self.changes.iter().filter(|c| c.id == id) {
+            let last_lvl = timeline.last().map(|(_, l)| *l).unwrap_or(0);
+            if c.lvl > last_lvl {
+                timeline.push((c.date, c.lvl));
+            }
+        }
+        timeline
+    }
+
+    // Keeps only the last change of each item before that date, to make room.
+    // The levels reached before that date are lost, the inventory after it is the same.
+    pub fn compact(&mut self, before: DateTime<Utc>) {
+        let (old, new): (Vec<_>, Vec<_>) = self.changes.drain(..).partition(|c| c.date < before);
+        let mut kept: Vec<HistoryChange> = vec![];
+        for change in old.into_iter().rev() {
+            if !kept.iter().any(|c| c.id == change.id) {
+                kept.push(change);
+            }
+        }
+        kept.retain(|c| c.lvl > 0);
+        kept.reverse();
+        self.changes = kept;
+        self.changes.extend(new);
+    }
+}
+
+fn apply(inv: &mut MktInventory, change: &HistoryChange) {
+    let i_type = match item_type_from_id(&change.id) {
+        Some(i_type) => i_type,
+        None => return,
+    };
+    if change.lvl == 0 {
+        inv.items_mut(i_type).remove(&change.id);
+    } else {
+        let mut item = OwnedItem::new(change.id.clone(), change.lvl, change.points);
+        item.added = None;
+        item.last_changed = None;
+        inv.items_mut(i_type).insert(change.id.clone(), item);
+    }
+}
+
+fn progress(data: &MktData, inv: &MktInventory, date: DateTime<Utc>) -> Progress {
+    Progress {
+        date,
+        owned_drivers: inv.drivers.len(),
+        owned_karts: inv.karts.len(),
+        owned_gliders: inv.gliders.len(),
+        covered_drivers: covered_courses(data, inv, ItemType::Driver).len(),
+        covered_karts: covered_courses(data, inv, ItemType::Kart).len(),
+        covered_gliders: covered_courses(data, inv, ItemType::Glider).len(),
+    }
+}
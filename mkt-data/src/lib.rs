@@ -1,25 +1,47 @@
 use std::{
-    collections::{BTreeMap, HashMap, HashSet},
+    collections::{hash_map::Entry, BTreeMap, HashMap, HashSet},
     convert::TryFrom,
-    error::Error,
     fmt::Display,
-    fs,
     iter::FromIterator,
 };
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
 use itertools::Itertools;
 use lazy_static::lazy_static;
 use regex::Regex;
 use serde::{Deserialize, Serialize, Serializer};
 use unidecode::unidecode;
 
+mod bundle;
+pub mod costs;
+pub mod coverage;
+pub mod diff;
+pub mod error;
+pub mod history;
+pub mod merge;
+pub mod migration;
+pub mod planner;
+pub mod rules;
+pub mod score;
+pub mod validation;
+
+use bundle::*;
+use error::*;
+use migration::*;
+use rules::*;
+
+pub type TourId = String;
 pub type CourseId = String;
 pub type ItemId = String;
 pub type ItemLvl = u8;
 pub type ItemPoints = u16;
+pub type SkillLvl = u8;
 pub type ItemHash = String;
 
+pub fn tour_id_from_name(name: &str) -> TourId {
+    "t_".to_string() + &id_from_name(name)
+}
+
 pub fn course_id_from_name(name: &str) -> CourseId {
     "c_".to_string() + &id_from_name(name)
 }
@@ -197,15 +219,82 @@ impl ToString for CourseGeneration {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Locale {
+    En,
+    Jp,
+    Fr,
+    De,
+    Es,
+    It,
+}
+impl TryFrom<&str> for Locale {
+    type Error = ();
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "en" | "english" => Ok(Locale::En),
+            "jp" | "ja" | "japanese" => Ok(Locale::Jp),
+            "fr" | "french" => Ok(Locale::Fr),
+            "de" | "german" => Ok(Locale::De),
+            "es" | "spanish" => Ok(Locale::Es),
+            "it" | "italian" => Ok(Locale::It),
+            _ => Err(()),
+        }
+    }
+}
+
+// used to compare names from different sources, ignoring case, accents and spacing
+pub fn name_key(name: &str) -> String {
+    unidecode(name).to_uppercase().split_whitespace().join(" ")
+}
+
+// Item ids by name key. Items of different types can share a name, like a driver and
+// a kart, those names only resolve when the item type is known.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ItemNameIndex {
+    pub names: HashMap<String, Vec<ItemId>>,
+}
+
+impl ItemNameIndex {
+    // none when unknown, or shared by several items of the type
+    pub fn get(&self, name: &str, i_type: Option<ItemType>) -> Option<&ItemId> {
+        let ids = self.names.get(&name_key(name))?;
+        let mut ids = ids
+            .iter()
+            .filter(|id| i_type.is_none() || item_type_from_id(id) == i_type);
+        match (ids.next(), ids.next()) {
+            (Some(id), None) => Some(id),
+            _ => None,
+        }
+    }
+
+    // names used by more than one item, sorted
+    pub fn collisions(&self) -> Vec<(&String, &Vec<ItemId>)> {
+        self.names
+            .iter()
+            .filter(|(_, ids)| ids.len() > 1)
+            .sorted()
+            .collect()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct Course {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub sort: Option<u32>,
     pub id: CourseId,
     pub name: String, // current english name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub names: BTreeMap<Locale, String>, // localized names
     #[serde(serialize_with = "ordered_set")]
     pub favorite_items: HashSet<ItemRequirement>,
-    #[serde(skip)]
+    // level 0 requirements, the item is only boosted a bit
+    #[serde(
+        default,
+        serialize_with = "ordered_set",
+        skip_serializing_if = "HashSet::is_empty"
+    )]
     pub favored_items: HashSet<ItemRequirement>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_changed: Option<DateTime<Utc>>,
@@ -216,6 +305,7 @@ impl Course {
             sort,
             id: course_id_from_name(&name),
             name,
+            names: BTreeMap::new(),
             favorite_items: HashSet::new(),
             favored_items: HashSet::new(),
             last_changed: None,
@@ -228,6 +318,7 @@ impl Course {
             sort,
             id,
             name,
+            names,
             favorite_items,
             favored_items,
             last_changed,
@@ -247,6 +338,10 @@ impl Course {
             self.name = name;
             changed = true;
         }
+        if !names.is_empty() && self.names != names {
+            self.names = names;
+            changed = true;
+        }
         if !favorite_items.is_empty() && self.favorite_items != favorite_items {
             self.favorite_items = favorite_items;
             changed = true;
@@ -260,6 +355,48 @@ impl Course {
             self.last_changed = last_changed.max(self.last_changed);
         }
     }
+
+    pub fn get_name(&self, locale: Locale) -> &str {
+        localized_name(&self.name, &self.names, locale)
+    }
+
+    pub fn all_names(&self) -> impl Iterator<Item = &str> {
+        Some(self.name.as_str())
+            .into_iter()
+            .chain(self.names.values().map(String::as_str))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct Tour {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sort: Option<u32>,
+    pub id: TourId,
+    pub name: String,
+    // first and last day, both included
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    // courses in rotation, R/T variants have their own id
+    pub courses: Vec<CourseId>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spotlight_items: Vec<ItemId>,
+}
+impl Tour {
+    pub fn new(name: String, start: NaiveDate, end: NaiveDate, sort: Option<u32>) -> Self {
+        Tour {
+            sort,
+            id: tour_id_from_name(&name),
+            name,
+            start,
+            end,
+            courses: vec![],
+            spotlight_items: vec![],
+        }
+    }
+
+    pub fn is_active(&self, date: NaiveDate) -> bool {
+        self.start <= date && date <= self.end
+    }
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
@@ -277,8 +414,20 @@ impl Display for ItemType {
         }
     }
 }
+impl TryFrom<&str> for ItemType {
+    type Error = ();
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value.to_lowercase().as_str() {
+            "d" | "driver" => Ok(ItemType::Driver),
+            "k" | "kart" => Ok(ItemType::Kart),
+            "g" | "glider" => Ok(ItemType::Glider),
+            _ => Err(()),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub enum Rarity {
     Normal,
     Super,
@@ -297,6 +446,26 @@ impl TryFrom<&str> for Rarity {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct Skill {
+    pub name: String,
+    // skill value at each skill level, empty when the page doesn't list them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub values: Vec<u32>,
+}
+impl Skill {
+    pub fn new(name: String) -> Self {
+        Skill {
+            name,
+            values: vec![],
+        }
+    }
+
+    pub fn value(&self, lvl: SkillLvl) -> Option<u32> {
+        self.values.get((lvl as usize).checked_sub(1)?).copied()
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct Item {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -304,10 +473,19 @@ pub struct Item {
     pub id: ItemId,
     pub i_type: ItemType,
     pub name: String, // current english name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub names: BTreeMap<Locale, String>, // localized names
     pub rarity: Rarity,
+    // special skill for drivers, bonus skill for karts and gliders
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skill: Option<Skill>,
     #[serde(serialize_with = "ordered_set")]
     pub favorite_courses: HashSet<CourseAvailability>,
-    #[serde(skip)]
+    #[serde(
+        default,
+        serialize_with = "ordered_set",
+        skip_serializing_if = "HashSet::is_empty"
+    )]
     pub favored_courses: HashSet<CourseAvailability>,
     pub hashes: Vec<ItemHash>, // used for screenshot import
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -320,7 +498,9 @@ impl Item {
             id: item_id_from_name(&name, i_type),
             i_type,
             name,
+            names: BTreeMap::new(),
             rarity,
+            skill: None,
             favorite_courses: HashSet::new(),
             favored_courses: HashSet::new(),
             hashes: vec![],
@@ -335,7 +515,9 @@ impl Item {
             id,
             i_type,
             name,
+            names,
             rarity,
+            skill,
             favorite_courses,
             favored_courses,
             hashes,
@@ -360,10 +542,18 @@ impl Item {
             self.name = name;
             changed = true;
         }
+        if !names.is_empty() && self.names != names {
+            self.names = names;
+            changed = true;
+        }
         if self.rarity != rarity {
             self.rarity = rarity;
             changed = true;
         }
+        if skill.is_some() && self.skill != skill {
+            self.skill = skill;
+            changed = true;
+        }
         if !favorite_courses.is_empty() && self.favorite_courses != favorite_courses {
             self.favorite_courses = favorite_courses;
             changed = true;
@@ -382,89 +572,17 @@ impl Item {
         }
     }
 
-    pub fn is_mii(&self) -> bool {
-        self.id.contains("_mii")
-    }
-
     pub fn valid_levels(&self) -> Vec<ItemLvl> {
         (1..=8).collect_vec()
     }
 
-    pub fn points_cap_tiers(&self) -> Vec<ItemPoints> {
-        if self.is_mii() {
-            return vec![500, 840, 1020, 1230, 1440];
-        }
-        match self.i_type {
-            ItemType::Driver => match self.rarity {
-                Rarity::Normal => vec![400, 600, 648, 704, 760],
-                Rarity::Super => vec![450, 675, 765, 870, 975],
-                Rarity::HighEnd => vec![500, 800, 980, 1190, 1400],
-            },
-            _ => match self.rarity {
-                Rarity::Normal => vec![200, 300, 324, 352, 380],
-                Rarity::Super => vec![220, 330, 366, 408, 450],
-                Rarity::HighEnd => vec![250, 400, 490, 595, 700],
-            },
-        }
-    }
-
-    pub fn valid_points(&self) -> Vec<ItemPoints> {
-        // special for mii
-        // 500 +12 or +10 840
-        // 840 +30 or +10 1020
-        // 1020 +30 or +10 1230
-        // 1230 +30 or +10 1440
-        if self.is_mii() {
-            return (500..840)
-                .step_by(2)
-                .chain((840..1020).step_by(10))
-                .chain((1020..1230).step_by(10))
-                .chain((1230..=1440).step_by(10))
-                .collect_vec();
-        }
-
-        match self.i_type {
-            ItemType::Driver => match self.rarity {
-                Rarity::Normal => (400..600)
-                    .step_by(8)
-                    .chain((600..648).step_by(8))
-                    .chain((648..704).step_by(8))
-                    .chain((704..=760).step_by(8))
-                    .collect_vec(),
-                Rarity::Super => (450..675)
-                    .step_by(9)
-                    .chain((675..765).step_by(15))
-                    .chain((765..870).step_by(15))
-                    .chain((870..=975).step_by(15))
-                    .collect_vec(),
-                Rarity::HighEnd => (500..800)
-                    .step_by(12)
-                    .chain((800..980).step_by(30))
-                    .chain((980..1190).step_by(30))
-                    .chain((1190..=1400).step_by(30))
-                    .collect_vec(),
-            },
-            _ => match self.rarity {
-                Rarity::Normal => (200..300)
-                    .step_by(4)
-                    .chain((300..324).step_by(4))
-                    .chain((324..352).step_by(4))
-                    .chain((352..=380).step_by(4))
-                    .collect_vec(),
-                Rarity::Super => (220..280)
-                    .step_by(4)
-                    .chain((280..330).step_by(5)) // this is a sneaky split
-                    .chain((330..366).step_by(6))
-                    .chain((366..408).step_by(6))
-                    .chain((408..=450).step_by(6))
-                    .collect_vec(),
-                Rarity::HighEnd => (250..400)
-                    .step_by(6)
-                    .chain((400..490).step_by(15))
-                    .chain((490..595).step_by(15))
-                    .chain((595..=700).step_by(15))
-                    .collect_vec(),
-            },
+    // one level per skill value, a single one when the values are unknown
+    pub fn valid_skill_levels(&self) -> Vec<SkillLvl> {
+        match &self.skill {
+            Some(skill) if !skill.values.is_empty() => {
+                (1..=skill.values.len() as SkillLvl).collect_vec()
+            }
+            _ => vec![1],
         }
     }
 
@@ -475,36 +593,105 @@ impl Item {
     pub fn get_reddit_name(&self) -> String {
         unidecode(&self.name)
     }
+
+    pub fn get_name(&self, locale: Locale) -> &str {
+        localized_name(&self.name, &self.names, locale)
+    }
+
+    pub fn all_names(&self) -> impl Iterator<Item = &str> {
+        Some(self.name.as_str())
+            .into_iter()
+            .chain(self.names.values().map(String::as_str))
+    }
 }
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+fn localized_name<'a>(
+    name: &'a str,
+    names: &'a BTreeMap<Locale, String>,
+    locale: Locale,
+) -> &'a str {
+    names.get(&locale).map(String::as_str).unwrap_or(name)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MktItemHashes {
+    #[serde(default)]
+    pub version: u32,
     #[serde(serialize_with = "ordered_map")]
     pub hashes: HashMap<ItemId, Vec<ItemHash>>,
 }
 
+impl Default for MktItemHashes {
+    fn default() -> Self {
+        MktItemHashes {
+            version: MKT_HASHES_VERSION,
+            hashes: HashMap::new(),
+        }
+    }
+}
+
 impl MktItemHashes {
     pub fn new() -> Self {
         Default::default()
     }
 
-    pub fn from_json(json: &str) -> Result<MktItemHashes, Box<dyn Error>> {
-        Ok(serde_json::from_str(json)?)
+    pub fn from_json(json: &str) -> Result<MktItemHashes, MktDataError> {
+        migrate_hashes(json)
+    }
+
+    pub fn to_json(&self) -> Result<String, MktDataError> {
+        serde_json::to_string_pretty(self).map_err(|source| MktDataError::Serialize { source })
+    }
+
+    pub fn load(file_name: &str) -> Result<MktItemHashes, MktDataError> {
+        let json = read_json(file_name)?;
+        with_path(MktItemHashes::from_json(&json), file_name)
+    }
+
+    pub fn save(&self, file_name: &str) -> Result<(), MktDataError> {
+        write_json(file_name, self.to_json()?)
+    }
+
+    pub fn from_bundle(bundle: &[u8]) -> Result<MktItemHashes, MktDataError> {
+        MktItemHashes::from_json(&bundle_to_json(bundle)?)
     }
 
-    pub fn to_json(&self) -> Result<String, Box<dyn Error>> {
-        Ok(serde_json::to_string_pretty(self)?)
+    pub fn to_bundle(&self) -> Result<Vec<u8>, MktDataError> {
+        to_bundle(self)
     }
 
-    pub fn load(file_name: &str) -> Result<MktItemHashes, Box<dyn Error>> {
-        let json = fs::read_to_string(file_name)?;
-        MktItemHashes::from_json(&json)
+    pub fn load_bundle(file_name: &str) -> Result<MktItemHashes, MktDataError> {
+        let bundle = read_bundle(file_name)?;
+        with_path(MktItemHashes::from_bundle(&bundle), file_name)
     }
 
-    pub fn save(&self, file_name: &str) -> Result<(), Box<dyn Error>> {
-        let json = serde_json::to_string_pretty(self)?;
-        fs::write(file_name, json)?;
-        Ok(())
+    pub fn save_bundle(&self, file_name: &str) -> Result<(), MktDataError> {
+        write_bundle(file_name, self.to_bundle()?)
+    }
+
+    // moves the hashes of renamed items to their current id
+    pub fn resolve_items(&mut self, data: &MktData) -> ResolvedItems {
+        let mut resolved = ResolvedItems::default();
+        for (id, mut hashes) in self
+            .hashes
+            .drain()
+            .sorted_by(|(a, _), (b, _)| a.cmp(b))
+            .collect_vec()
+        {
+            let new_id = match data.resolve_item_id(&id) {
+                Some(new_id) if new_id != id => {
+                    resolved.remapped.push((id, new_id.clone()));
+                    new_id
+                }
+                Some(_) => id,
+                None => {
+                    resolved.unresolved.push(id.clone());
+                    id
+                }
+            };
+            self.hashes.entry(new_id).or_default().append(&mut hashes);
+        }
+        resolved
     }
 
     pub fn merge(&mut self, mut new_hashes: MktItemHashes) {
@@ -536,8 +723,10 @@ impl FromIterator<(ItemId, Vec<ItemHash>)> for MktItemHashes {
     }
 }
 
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MktData {
+    #[serde(default)]
+    pub version: u32,
     #[serde(serialize_with = "ordered_map")]
     pub courses: HashMap<CourseId, Course>,
     #[serde(serialize_with = "ordered_map")]
@@ -546,25 +735,73 @@ pub struct MktData {
     pub karts: HashMap<ItemId, Item>,
     #[serde(serialize_with = "ordered_map")]
     pub gliders: HashMap<ItemId, Item>,
+    // old item ids to current ones, for items renamed on the wiki
+    #[serde(
+        default,
+        serialize_with = "ordered_map",
+        skip_serializing_if = "HashMap::is_empty"
+    )]
+    pub aliases: HashMap<ItemId, ItemId>,
+    #[serde(
+        default,
+        serialize_with = "ordered_map",
+        skip_serializing_if = "HashMap::is_empty"
+    )]
+    pub tours: HashMap<TourId, Tour>,
+    #[serde(default)]
+    pub rules: PointRules,
+}
+impl Default for MktData {
+    fn default() -> Self {
+        MktData {
+            version: MKT_DATA_VERSION,
+            courses: HashMap::new(),
+            drivers: HashMap::new(),
+            karts: HashMap::new(),
+            gliders: HashMap::new(),
+            aliases: HashMap::new(),
+            tours: HashMap::new(),
+            rules: PointRules::default(),
+        }
+    }
 }
 impl MktData {
     pub fn new() -> Self {
         Default::default()
     }
 
-    pub fn from_json(json: &str) -> Result<MktData, Box<dyn Error>> {
-        Ok(serde_json::from_str(json)?)
+    pub fn from_json(json: &str) -> Result<MktData, MktDataError> {
+        migrate_data(json)
     }
 
-    pub fn load(file_name: &str) -> Result<MktData, Box<dyn Error>> {
-        let json = fs::read_to_string(file_name)?;
-        MktData::from_json(&json)
+    pub fn to_json(&self) -> Result<String, MktDataError> {
+        serde_json::to_string_pretty(self).map_err(|source| MktDataError::Serialize { source })
     }
 
-    pub fn save(&self, file_name: &str) -> Result<(), Box<dyn Error>> {
-        let json = serde_json::to_string_pretty(self)?;
-        fs::write(file_name, json)?;
-        Ok(())
+    pub fn load(file_name: &str) -> Result<MktData, MktDataError> {
+        let json = read_json(file_name)?;
+        with_path(MktData::from_json(&json), file_name)
+    }
+
+    pub fn save(&self, file_name: &str) -> Result<(), MktDataError> {
+        write_json(file_name, self.to_json()?)
+    }
+
+    pub fn from_bundle(bundle: &[u8]) -> Result<MktData, MktDataError> {
+        MktData::from_json(&bundle_to_json(bundle)?)
+    }
+
+    pub fn to_bundle(&self) -> Result<Vec<u8>, MktDataError> {
+        to_bundle(self)
+    }
+
+    pub fn load_bundle(file_name: &str) -> Result<MktData, MktDataError> {
+        let bundle = read_bundle(file_name)?;
+        with_path(MktData::from_bundle(&bundle), file_name)
+    }
+
+    pub fn save_bundle(&self, file_name: &str) -> Result<(), MktDataError> {
+        write_bundle(file_name, self.to_bundle()?)
     }
 
     pub fn get_item(&self, id: &str) -> Option<&Item> {
@@ -574,11 +811,104 @@ impl MktData {
             .or_else(|| self.gliders.get(id))
     }
 
-    pub fn merge_hashes(&mut self, MktItemHashes { hashes }: &MktItemHashes) {
-        let types = [&mut self.drivers, &mut self.karts, &mut self.gliders];
-        for list in types {
-            for item in list.values_mut() {
-                if let Some(hash) = hashes.get(&item.id) {
+    pub fn get_item_mut(&mut self, id: &str) -> Option<&mut Item> {
+        match item_type_from_id(id)? {
+            ItemType::Driver => self.drivers.get_mut(id),
+            ItemType::Kart => self.karts.get_mut(id),
+            ItemType::Glider => self.gliders.get_mut(id),
+        }
+    }
+
+    pub fn point_ladder(&self, item: &Item) -> Option<&PointLadder> {
+        self.rules.ladder(item)
+    }
+
+    pub fn current_tour(&self, now: DateTime<Utc>) -> Option<&Tour> {
+        let today = now.date_naive();
+        self.tours.values().find(|t| t.is_active(today))
+    }
+
+    // the same data, with only the courses of the tour
+    pub fn for_tour(&self, tour: &Tour) -> MktData {
+        let mut data = self.clone();
+        data.courses.retain(|id, _| tour.courses.contains(id));
+        for item in data
+            .drivers
+            .values_mut()
+            .chain(data.karts.values_mut())
+            .chain(data.gliders.values_mut())
+        {
+            item.favorite_courses
+                .retain(|c| tour.courses.contains(&c.id));
+            item.favored_courses
+                .retain(|c| tour.courses.contains(&c.id));
+        }
+        data
+    }
+
+    // follows the aliases to the current id, none if the item is not in the data
+    pub fn resolve_item_id(&self, id: &str) -> Option<ItemId> {
+        let mut id = id;
+        // a chain can't be longer than the table, this also stops on cycles
+        for _ in 0..=self.aliases.len() {
+            if self.get_item(id).is_some() {
+                return Some(id.to_string());
+            }
+            id = self.aliases.get(id)?;
+        }
+        None
+    }
+
+    pub fn items(&self, i_type: ItemType) -> &HashMap<ItemId, Item> {
+        match i_type {
+            ItemType::Driver => &self.drivers,
+            ItemType::Kart => &self.karts,
+            ItemType::Glider => &self.gliders,
+        }
+    }
+
+    // finds an item from its name in any locale
+    pub fn find_item_by_name(&self, name: &str) -> Option<&Item> {
+        let key = name_key(name);
+        self.drivers
+            .values()
+            .chain(self.karts.values())
+            .chain(self.gliders.values())
+            .find(|i| i.all_names().any(|n| name_key(n) == key))
+    }
+
+    // finds a course from its name in any locale
+    pub fn find_course_by_name(&self, name: &str) -> Option<&Course> {
+        let key = name_key(name);
+        self.courses
+            .values()
+            .find(|c| c.all_names().any(|n| name_key(n) == key))
+    }
+
+    // names in every locale to item id, for importers matching a lot of names
+    pub fn item_name_index(&self) -> ItemNameIndex {
+        let mut index = ItemNameIndex::default();
+        for item in self
+            .drivers
+            .values()
+            .chain(self.karts.values())
+            .chain(self.gliders.values())
+            .sorted_by_key(|i| &i.id)
+        {
+            for name in item.all_names() {
+                let ids = index.names.entry(name_key(name)).or_default();
+                if !ids.contains(&item.id) {
+                    ids.push(item.id.clone());
+                }
+            }
+        }
+        index
+    }
+
+    pub fn merge_hashes(&mut self, MktItemHashes { hashes, .. }: &MktItemHashes) {
+        for (id, hash) in hashes.iter().sorted_by_key(|(id, _)| *id) {
+            if let Some(id) = self.resolve_item_id(id) {
+                if let Some(item) = self.get_item_mut(&id) {
                     item.hashes.extend(hash.clone());
                 }
             }
@@ -627,6 +957,10 @@ impl MktData {
             }
         }
         self.gliders.extend(new_data.gliders);
+
+        self.aliases.extend(new_data.aliases);
+        self.tours.extend(new_data.tours);
+        self.rules.merge(new_data.rules);
     }
 }
 
@@ -635,6 +969,9 @@ pub struct OwnedItem {
     pub id: ItemId,
     pub lvl: ItemLvl,
     pub points: ItemPoints,
+    // none when unknown, screenshots don't show it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub skill_lvl: Option<SkillLvl>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub added: Option<DateTime<Utc>>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -647,6 +984,7 @@ impl OwnedItem {
             id,
             lvl,
             points,
+            skill_lvl: None,
             added: Some(Utc::now()),
             last_changed: Some(Utc::now()),
         }
@@ -659,6 +997,7 @@ impl OwnedItem {
             id,
             lvl,
             points,
+            skill_lvl,
             added,
             last_changed,
         }: OwnedItem,
@@ -677,6 +1016,10 @@ impl OwnedItem {
             self.points = points;
             changed = true;
         }
+        if skill_lvl.is_some() && self.skill_lvl != skill_lvl {
+            self.skill_lvl = skill_lvl;
+            changed = true;
+        }
 
         if changed {
             self.last_changed = last_changed.max(self.last_changed);
@@ -693,6 +1036,7 @@ impl OwnedItem {
             id,
             lvl,
             points,
+            skill_lvl,
             added,
             last_changed,
         }: OwnedItem,
@@ -711,6 +1055,10 @@ impl OwnedItem {
             self.points = points;
             changed = true;
         }
+        if self.skill_lvl < skill_lvl {
+            self.skill_lvl = skill_lvl;
+            changed = true;
+        }
 
         if changed {
             self.last_changed = last_changed.max(self.last_changed);
@@ -720,47 +1068,70 @@ impl OwnedItem {
         }
     }
 
-    pub fn normalize_points(&mut self, item: &Item) {
-        let incs = item.valid_points();
-        let max_points = *incs.last().expect("max points");
+    pub fn normalize_points(&mut self, ladder: &PointLadder) {
+        let incs = ladder.valid_points();
         for (a, b) in Some(0).iter().chain(incs.iter()).tuple_windows() {
             if (a + 1..=*b).contains(&self.points) {
                 self.points = *b;
                 break;
             }
         }
-        self.points = self.points.max(0).min(max_points);
+        if let Some(max_points) = incs.last() {
+            self.points = self.points.min(*max_points);
+        }
     }
 
-    pub fn increment_points(&mut self, item: &Item) {
-        let incs = item.valid_points();
-        self.points = incs
+    pub fn increment_points(&mut self, ladder: &PointLadder) {
+        self.points = ladder
+            .valid_points()
             .into_iter()
             .find(|p| *p > self.points)
             .unwrap_or(self.points);
     }
-    pub fn decrement_points(&mut self, item: &Item) {
-        let incs = item.valid_points();
-        self.points = incs
+    pub fn decrement_points(&mut self, ladder: &PointLadder) {
+        self.points = ladder
+            .valid_points()
             .into_iter()
             .rev()
             .find(|p| *p < self.points)
             .unwrap_or(self.points);
     }
 
-    pub fn point_cap_tier(&self, item: &Item) -> u8 {
-        let caps = item.points_cap_tiers();
-        for (l, cap) in caps.into_iter().skip(1).enumerate() {
-            if self.points <= cap {
-                return l as u8;
-            }
-        }
-        0
+    // favorite courses reached when going from the current level to lvl
+    pub fn level_up_courses<'a>(
+        &self,
+        item: &'a Item,
+        lvl: ItemLvl,
+    ) -> impl Iterator<Item = &'a CourseAvailability> {
+        let current = self.lvl;
+        item.favorite_courses
+            .iter()
+            .filter(move |c| current < c.lvl && c.lvl <= lvl)
+    }
+
+    pub fn point_cap_tier(&self, ladder: &PointLadder) -> u8 {
+        ladder.cap_tier(self.points)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ResolvedItems {
+    // old and current id of the renamed items
+    pub remapped: Vec<(ItemId, ItemId)>,
+    // ids not in the data, even through the aliases
+    pub unresolved: Vec<ItemId>,
+}
+
+impl ResolvedItems {
+    pub fn is_empty(&self) -> bool {
+        self.remapped.is_empty() && self.unresolved.is_empty()
     }
 }
 
-#[derive(Debug, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MktInventory {
+    #[serde(default)]
+    pub version: u32,
     #[serde(serialize_with = "ordered_map")]
     pub drivers: HashMap<ItemId, OwnedItem>,
     #[serde(serialize_with = "ordered_map")]
@@ -768,22 +1139,96 @@ pub struct MktInventory {
     #[serde(serialize_with = "ordered_map")]
     pub gliders: HashMap<ItemId, OwnedItem>,
 }
+impl Default for MktInventory {
+    fn default() -> Self {
+        MktInventory {
+            version: MKT_INVENTORY_VERSION,
+            drivers: HashMap::new(),
+            karts: HashMap::new(),
+            gliders: HashMap::new(),
+        }
+    }
+}
 impl MktInventory {
     pub fn new() -> Self {
         Default::default()
     }
 
-    pub fn from_json(json: &str) -> Result<MktInventory, Box<dyn Error>> {
-        Ok(serde_json::from_str(json)?)
+    pub fn from_json(json: &str) -> Result<MktInventory, MktDataError> {
+        migrate_inventory(json)
+    }
+
+    // from_json, then the renamed items are remapped with the aliases of the data
+    pub fn from_json_with_data(
+        json: &str,
+        data: &MktData,
+    ) -> Result<(MktInventory, ResolvedItems), MktDataError> {
+        let mut inv = MktInventory::from_json(json)?;
+        let resolved = inv.resolve_items(data);
+        Ok((inv, resolved))
+    }
+
+    pub fn to_json(&self) -> Result<String, MktDataError> {
+        serde_json::to_string_pretty(self).map_err(|source| MktDataError::Serialize { source })
+    }
+
+    pub fn load(file_name: &str) -> Result<MktInventory, MktDataError> {
+        let json = read_json(file_name)?;
+        with_path(MktInventory::from_json(&json), file_name)
     }
 
-    pub fn to_json(&self) -> Result<String, Box<dyn Error>> {
-        Ok(serde_json::to_string_pretty(self)?)
+    pub fn save(&self, file_name: &str) -> Result<(), MktDataError> {
+        write_json(file_name, self.to_json()?)
     }
 
-    pub fn load(file_name: &str) -> Result<MktInventory, Box<dyn Error>> {
-        let json = fs::read_to_string(file_name)?;
-        MktInventory::from_json(&json)
+    pub fn get_item(&self, id: &str) -> Option<&OwnedItem> {
+        self.drivers
+            .get(id)
+            .or_else(|| self.karts.get(id))
+            .or_else(|| self.gliders.get(id))
+    }
+
+    pub fn items(&self, i_type: ItemType) -> &HashMap<ItemId, OwnedItem> {
+        match i_type {
+            ItemType::Driver => &self.drivers,
+            ItemType::Kart => &self.karts,
+            ItemType::Glider => &self.gliders,
+        }
+    }
+
+    fn items_mut(&mut self, i_type: ItemType) -> &mut HashMap<ItemId, OwnedItem> {
+        match i_type {
+            ItemType::Driver => &mut self.drivers,
+            ItemType::Kart => &mut self.karts,
+            ItemType::Glider => &mut self.gliders,
+        }
+    }
+
+    // renames the items with an old id, unknown items are kept but reported
+    pub fn resolve_items(&mut self, data: &MktData) -> ResolvedItems {
+        let mut resolved = ResolvedItems::default();
+        for i_type in [ItemType::Driver, ItemType::Kart, ItemType::Glider] {
+            let items = self.items_mut(i_type).drain().collect_vec();
+            for (id, mut item) in items.into_iter().sorted_by(|(a, _), (b, _)| a.cmp(b)) {
+                match data.resolve_item_id(&id) {
+                    Some(new_id) if new_id != id => {
+                        resolved.remapped.push((id, new_id.clone()));
+                        item.id = new_id;
+                    }
+                    Some(_) => {}
+                    None => resolved.unresolved.push(id),
+                }
+                // an item owned under both ids keeps the best of each
+                let i_type = item_type_from_id(&item.id).unwrap_or(i_type);
+                match self.items_mut(i_type).entry(item.id.clone()) {
+                    Entry::Occupied(mut e) => e.get_mut().improve(item),
+                    Entry::Vacant(e) => {
+                        e.insert(item);
+                    }
+                }
+            }
+        }
+        resolved
     }
 
     pub fn from_item(i_type: ItemType, item: OwnedItem) -> Self {
@@ -802,15 +1247,31 @@ impl MktInventory {
         inv
     }
 
-    pub fn from_items(items: Vec<OwnedItem>, data: &MktData) -> Self {
-        let mut items = items.into_iter().into_group_map_by(|i| {
+    // unknown items are left out, and reported
+    pub fn from_items(items: Vec<OwnedItem>, data: &MktData) -> (Self, ResolvedItems) {
+        let mut resolved = ResolvedItems::default();
+        let items = items.into_iter().filter_map(|mut i| {
+            match data.resolve_item_id(&i.id) {
+                Some(id) if id != i.id => {
+                    resolved.remapped.push((i.id.clone(), id.clone()));
+                    i.id = id;
+                }
+                Some(_) => {}
+                None => {
+                    resolved.unresolved.push(i.id);
+                    return None;
+                }
+            }
+            Some(i)
+        });
+        let mut items = items.into_group_map_by(|i| {
             data.drivers
                 .get(&i.id)
                 .or_else(|| data.karts.get(&i.id))
                 .or_else(|| data.gliders.get(&i.id))
                 .map(|i| i.i_type)
         });
-        MktInventory {
+        let inv = MktInventory {
             drivers: items
                 .remove(&Some(ItemType::Driver))
                 .unwrap_or_default()
@@ -829,7 +1290,9 @@ impl MktInventory {
                 .into_iter()
                 .map(|i| (i.id.clone(), i))
                 .collect(),
-        }
+            ..MktInventory::new()
+        };
+        (inv, resolved)
     }
 
     pub fn update_inventory(&mut self, mut new_inv: MktInventory) {
@@ -0,0 +1,139 @@
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+
+use crate::{ItemId, ItemType, MktInventory, OwnedItem};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeSide {
+    Local,
+    Incoming,
+}
+
+// an item changed differently on both sides since the base
+#[derive(Debug, Clone)]
+pub struct MergeConflict {
+    pub id: ItemId,
+    pub i_type: ItemType,
+    // none when the item was removed on that side
+    pub local: Option<OwnedItem>,
+    pub incoming: Option<OwnedItem>,
+    pub kept: MergeSide,
+}
+
+#[derive(Debug, Clone)]
+pub struct InventoryMerge {
+    pub inventory: MktInventory,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl MktInventory {
+    // Three-way merge of another inventory, like a backup from another device, into
+    // this one. The base is the inventory both started from, usually the last export.
+    // Without a base every difference is a conflict. Conflicts go to the most recent
+    // change, local edits win when the dates are unknown, and edits win over removals.
+    pub fn merge_three_way(
+        &self,
+        base: Option<&MktInventory>,
+        incoming: &MktInventory,
+    ) -> InventoryMerge {
+        let mut inventory = MktInventory::new();
+        let mut conflicts = vec![];
+        // removals leave no date behind, the last change of the inventory is the best guess
+        let incoming_date = latest_change(incoming);
+
+        for i_type in [ItemType::Driver, ItemType::Kart, ItemType::Glider] {
+            let base_items = base.map(|b| b.items(i_type));
+            let (local_items, incoming_items) = (self.items(i_type), incoming.items(i_type));
+            let ids = local_items
+                .keys()
+                .chain(incoming_items.keys())
+                .chain(base_items.into_iter().flat_map(|b| b.keys()))
+                .unique()
+                .sorted()
+                .cloned()
+                .collect_vec();
+
+            for id in ids {
+                let base = base_items.and_then(|b| b.get(&id));
+                let local = local_items.get(&id);
+                let incoming = incoming_items.get(&id);
+
+                let local_changed = changed(base, local, None);
+                let incoming_changed = changed(base, incoming, incoming_date);
+                let item = if same_state(local, incoming) || !incoming_changed {
+                    local
+                } else if !local_changed {
+                    incoming
+                } else {
+                    let kept = match (local, incoming) {
+                        (Some(l), Some(i)) if i.last_changed > l.last_changed => {
+                            MergeSide::Incoming
+                        }
+                        (None, Some(_)) => MergeSide::Incoming,
+                        _ => MergeSide::Local,
+                    };
+                    conflicts.push(MergeConflict {
+                        id: id.clone(),
+                        i_type,
+                        local: local.cloned(),
+                        incoming: incoming.cloned(),
+                        kept,
+                    });
+                    match kept {
+                        MergeSide::Local => local,
+                        MergeSide::Incoming => incoming,
+                    }
+                };
+
+                if let Some(item) = item {
+                    inventory.items_mut(i_type).insert(id, item.clone());
+                }
+            }
+        }
+
+        InventoryMerge {
+            inventory,
+            conflicts,
+        }
+    }
+}
+
+// dates are ignored
+fn same_state(a: Option<&OwnedItem>, b: Option<&OwnedItem>) -> bool {
+    match (a, b) {
+        (Some(a), Some(b)) => a.lvl == b.lvl && a.points == b.points && a.skill_lvl == b.skill_lvl,
+        (a, b) => a.is_none() && b.is_none(),
+    }
+}
+
+// An item older than the base is not a change, like in an old backup.
+// Removals are dated with the removal date, when known.
+fn changed(
+    base: Option<&OwnedItem>,
+    item: Option<&OwnedItem>,
+    removal_date: Option<DateTime<Utc>>,
+) -> bool {
+    if same_state(base, item) {
+        return false;
+    }
+    match (base, item) {
+        (Some(base), Some(item)) => match (base.last_changed, item.last_changed) {
+            (Some(base_date), Some(date)) => date >= base_date,
+            _ => true,
+        },
+        (Some(base), None) => match (base.added.max(base.last_changed), removal_date) {
+            (Some(base_date), Some(date)) => date >= base_date,
+            _ => true,
+        },
+        _ => true,
+    }
+}
+
+fn latest_change(inv: &MktInventory) -> Option<DateTime<Utc>> {
+    inv.drivers
+        .values()
+        .chain(inv.karts.values())
+        .chain(inv.gliders.values())
+        .flat_map(|i| i.added.max(i.last_changed))
+        .max()
+}
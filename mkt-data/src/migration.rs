@@ -0,0 +1,78 @@
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{
+    error::MktDataError,
+    rules::{PointRules, MII_LADDER},
+};
+
+// A migration upgrades a document from its index version to the next one.
+// Documents written before versioning have no version field and are version 0.
+type Migration = fn(&mut Value);
+
+const DATA_MIGRATIONS: &[Migration] = &[unversioned, point_rules];
+const INVENTORY_MIGRATIONS: &[Migration] = &[unversioned];
+const HASHES_MIGRATIONS: &[Migration] = &[unversioned];
+// the history was versioned from the start
+const HISTORY_MIGRATIONS: &[Migration] = &[];
+
+pub const MKT_DATA_VERSION: u32 = DATA_MIGRATIONS.len() as u32;
+pub const MKT_INVENTORY_VERSION: u32 = INVENTORY_MIGRATIONS.len() as u32;
+pub const MKT_HASHES_VERSION: u32 = HASHES_MIGRATIONS.len() as u32;
+pub const MKT_HISTORY_VERSION: u32 = HISTORY_MIGRATIONS.len() as u32;
+
+// same layout, only the version field was added
+fn unversioned(_: &mut Value) {}
+
+// the point ladders were hardcoded, the Miis were found by their id
+fn point_rules(value: &mut Value) {
+    let mut rules = PointRules::default();
+    if let Some(drivers) = value.get("drivers").and_then(Value::as_object) {
+        for id in drivers.keys().filter(|id| id.contains("_mii")) {
+            rules.items.insert(id.clone(), MII_LADDER.into());
+        }
+    }
+    if let Some(object) = value.as_object_mut() {
+        object.insert(
+            "rules".into(),
+            serde_json::to_value(rules).expect("rules to json"),
+        );
+    }
+}
+
+fn migrate<T: DeserializeOwned>(json: &str, migrations: &[Migration]) -> Result<T, MktDataError> {
+    let mut value: Value = serde_json::from_str(json)?;
+
+    let version = value.get("version").and_then(Value::as_u64).unwrap_or(0);
+    if version > migrations.len() as u64 {
+        return Err(MktDataError::UnsupportedVersion {
+            path: None,
+            version,
+            supported: migrations.len() as u32,
+        });
+    }
+    for migration in &migrations[version as usize..] {
+        migration(&mut value);
+    }
+    if let Some(object) = value.as_object_mut() {
+        object.insert("version".into(), migrations.len().into());
+    }
+
+    Ok(serde_path_to_error::deserialize(value)?)
+}
+
+pub(crate) fn migrate_data<T: DeserializeOwned>(json: &str) -> Result<T, MktDataError> {
+    migrate(json, DATA_MIGRATIONS)
+}
+
+pub(crate) fn migrate_inventory<T: DeserializeOwned>(json: &str) -> Result<T, MktDataError> {
+    migrate(json, INVENTORY_MIGRATIONS)
+}
+
+pub(crate) fn migrate_hashes<T: DeserializeOwned>(json: &str) -> Result<T, MktDataError> {
+    migrate(json, HASHES_MIGRATIONS)
+}
+
+pub(crate) fn migrate_history<T: DeserializeOwned>(json: &str) -> Result<T, MktDataError> {
+    migrate(json, HISTORY_MIGRATIONS)
+}
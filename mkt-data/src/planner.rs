@@ -0,0 +1,205 @@
+use std::collections::{HashMap, HashSet};
+
+use itertools::Itertools;
+
+use crate::{item_type_from_id, CourseId, ItemId, ItemLvl, ItemType, MktData, MktInventory};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlannedItem {
+    pub id: ItemId,
+    pub i_type: ItemType,
+    // level needed to cover all the courses below
+    pub lvl: ItemLvl,
+    // none when the item is not owned yet
+    pub owned_lvl: Option<ItemLvl>,
+    pub courses: Vec<CourseId>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UncoverableCourse {
+    pub id: CourseId,
+    pub i_type: ItemType,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CoveragePlan {
+    // items to get or to level up, owned items already covering courses are not listed
+    pub items: Vec<PlannedItem>,
+    // courses without any favorite item of that type in the data
+    pub uncoverable: Vec<UncoverableCourse>,
+}
+
+impl CoveragePlan {
+    pub fn items_of_type(&self, i_type: ItemType) -> impl Iterator<Item = &PlannedItem> {
+        self.items.iter().filter(move |i| i.i_type == i_type)
+    }
+
+    pub fn new_items(&self) -> impl Iterator<Item = &PlannedItem> {
+        self.items.iter().filter(|i| i.owned_lvl.is_none())
+    }
+
+    pub fn level_ups(&self) -> impl Iterator<Item = &PlannedItem> {
+        self.items.iter().filter(|i| i.owned_lvl.is_some())
+    }
+}
+
+// Finds a small set of items covering every course, starting from the inventory.
+// This is a set cover problem, solved with the usual greedy approximation:
+// pick the item covering the most uncovered courses until nothing is left.
+// Ties go to owned items, then to the lowest level needed, then to the data order.
+pub fn plan_coverage(data: &MktData, inv: &MktInventory) -> CoveragePlan {
+    let mut plan = CoveragePlan::default();
+    for i_type in [ItemType::Driver, ItemType::Kart, ItemType::Glider] {
+        plan_item_type(data, inv, i_type, &mut plan);
+    }
+    plan
+}
+
+fn plan_item_type(data: &MktData, inv: &MktInventory, i_type: ItemType, plan: &mut CoveragePlan) {
+    let items = data.items(i_type);
+    let owned = inv.items(i_type);
+    let owned_lvl = |id: &str| owned.get(id).map(|i| i.lvl).unwrap_or(0);
+
+    // courses left to cover, and what each item could still cover with the level needed
+    let mut uncovered = HashSet::new();
+    let mut candidates: HashMap<&ItemId, Vec<(&CourseId, ItemLvl)>> = HashMap::new();
+    for course in data.courses.values().sorted_by_key(|c| (c.sort, &c.id)) {
+        let reqs = course
+            .favorite_items
+            .iter()
+            .filter(|r| item_type_from_id(&r.id) == Some(i_type) && items.contains_key(&r.id))
+            .collect_vec();
+        if reqs.is_empty() {
+            plan.uncoverable.push(UncoverableCourse {
+                id: course.id.clone(),
+                i_type,
+            });
+        } else if !reqs.iter().any(|r| owned_lvl(&r.id) >= r.lvl) {
+            uncovered.insert(&course.id);
+            for r in reqs {
+                candidates
+                    .entry(&r.id)
+                    .or_default()
+                    .push((&course.id, r.lvl));
+            }
+        }
+    }
+
+    while !uncovered.is_empty() {
+        let best = candidates
+            .iter()
+            .map(|(id, courses)| {
+                let new_courses = courses
+                    .iter()
+                    .filter(|(c, _)| uncovered.contains(*c))
+                    .collect_vec();
+                let lvl = new_courses.iter().map(|(_, l)| *l).max().unwrap_or(0);
+                (*id, new_courses.len(), lvl)
+            })
+            .filter(|(_, count, _)| *count > 0)
+            .max_by_key(|(id, count, lvl)| {
+                (
+                    *count,
+                    owned.contains_key(*id),
+                    std::cmp::Reverse(*lvl),
+                    std::cmp::Reverse((items[*id].sort, *id)),
+                )
+            })
+            .map(|(id, _, _)| id);
+
+        let id = match best {
+            Some(id) => id,
+            None => break,
+        };
+        let courses = candidates.remove(id).unwrap_or_default();
+        let courses = courses
+            .into_iter()
+            .filter(|(c, _)| uncovered.remove(*c))
+            .collect_vec();
+        plan.items.push(PlannedItem {
+            id: id.clone(),
+            i_type,
+            lvl: courses.iter().map(|(_, l)| *l).max().unwrap_or(0),
+            owned_lvl: owned.get(id).map(|i| i.lvl),
+            courses: courses
+                .into_iter()
+                .map(|(c, _)| c.clone())
+                .sorted()
+                .collect(),
+        });
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LevelUp {
+    pub id: ItemId,
+    pub i_type: ItemType,
+    pub from: ItemLvl,
+    pub to: ItemLvl,
+    // uncovered courses this level up would cover
+    pub courses: Vec<CourseId>,
+}
+
+// Ranks every level up of the owned items by the number of course slots it would fill.
+// A slot is a course and an item type, it is filled when an owned item has the level
+// required by the course, as in the coverage page. Each item only gets the levels
+// filling more than the level below, level ups filling nothing are left out.
+pub fn rank_level_ups(data: &MktData, inv: &MktInventory) -> Vec<LevelUp> {
+    let mut level_ups = vec![];
+    for i_type in [ItemType::Driver, ItemType::Kart, ItemType::Glider] {
+        let covered = covered_courses(data, inv, i_type);
+        for (owned, item) in inv
+            .items(i_type)
+            .values()
+            .filter_map(|o| data.items(i_type).get(&o.id).map(|i| (o, i)))
+        {
+            // only the levels filling more courses than the level before
+            let mut filled = 0;
+            for lvl in item.valid_levels().into_iter().filter(|l| *l > owned.lvl) {
+                let courses = owned
+                    .level_up_courses(item, lvl)
+                    .filter(|c| data.courses.contains_key(&c.id) && !covered.contains(&c.id))
+                    .map(|c| c.id.clone())
+                    .sorted()
+                    .collect_vec();
+                if courses.len() > filled {
+                    filled = courses.len();
+                    level_ups.push(LevelUp {
+                        id: item.id.clone(),
+                        i_type,
+                        from: owned.lvl,
+                        to: lvl,
+                        courses,
+                    });
+                }
+            }
+        }
+    }
+    level_ups.sort_by(|a, b| {
+        b.courses
+            .len()
+            .cmp(&a.courses.len())
+            .then((a.to - a.from).cmp(&(b.to - b.from)))
+            .then(a.i_type.cmp(&b.i_type))
+            .then(a.id.cmp(&b.id))
+    });
+    level_ups
+}
+
+pub fn covered_courses<'a>(
+    data: &'a MktData,
+    inv: &MktInventory,
+    i_type: ItemType,
+) -> HashSet<&'a CourseId> {
+    let owned = inv.items(i_type);
+    data.courses
+        .values()
+        .filter(|c| {
+            c.favorite_items.iter().any(|r| {
+                item_type_from_id(&r.id) == Some(i_type)
+                    && owned.get(&r.id).map(|i| i.lvl).unwrap_or(0) >= r.lvl
+            })
+        })
+        .map(|c| &c.id)
+        .collect()
+}
@@ -0,0 +1,209 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{score::ScoreWeights, Item, ItemId, ItemPoints, ItemType, Rarity};
+
+pub const MII_LADDER: &str = "mii";
+
+// points gained per increment, from a points value until the next step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PointStep {
+    pub from: ItemPoints,
+    pub step: ItemPoints,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PointLadder {
+    // base points, then the max points of each cap tier
+    pub caps: Vec<ItemPoints>,
+    // sorted by points, the last step goes up to the last cap
+    pub steps: Vec<PointStep>,
+}
+
+impl PointLadder {
+    pub fn new(caps: &[ItemPoints], steps: &[(ItemPoints, ItemPoints)]) -> Self {
+        PointLadder {
+            caps: caps.to_vec(),
+            steps: steps
+                .iter()
+                .map(|(from, step)| PointStep {
+                    from: *from,
+                    step: *step,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn max_points(&self) -> ItemPoints {
+        self.caps.last().copied().unwrap_or_default()
+    }
+
+    pub fn valid_points(&self) -> Vec<ItemPoints> {
+        let end = self.max_points() as usize + 1;
+        let mut points = vec![];
+        for (i, s) in self.steps.iter().enumerate() {
+            let next = self
+                .steps
+                .get(i + 1)
+                .map(|n| n.from as usize)
+                .unwrap_or(end);
+            points.extend(
+                (s.from as usize..next.min(end))
+                    .step_by(s.step.max(1) as usize)
+                    .map(|p| p as ItemPoints),
+            );
+        }
+        points
+    }
+
+    // 0 when the points are above all the caps
+    pub fn cap_tier(&self, points: ItemPoints) -> u8 {
+        for (l, cap) in self.caps.iter().skip(1).enumerate() {
+            if points <= *cap {
+                return l as u8;
+            }
+        }
+        0
+    }
+}
+
+// Resources to go up one step, the level steps start at level 1 and the cap steps
+// at tier 0. The defaults are the same for every ladder, the tickets and Grand Stars
+// are of the item rarity. They can be corrected in the data, the updates keep them.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UpgradeCosts {
+    pub level_duplicates: Vec<u32>,
+    pub cap_tickets: Vec<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cap_grand_stars: Vec<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PointRules {
+    // items use the ladder named after their type and rarity, like "kart_high_end"
+    pub ladders: BTreeMap<String, PointLadder>,
+    // items using another ladder, like the Miis
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub items: BTreeMap<ItemId, String>,
+    // upgrade costs by ladder name, items without one have unknown costs
+    #[serde(default = "default_costs", skip_serializing_if = "BTreeMap::is_empty")]
+    pub costs: BTreeMap<String, UpgradeCosts>,
+    // base points kept on each shelf of a course, for the scores, the defaults when not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub score_weights: Option<ScoreWeights>,
+}
+
+impl Default for PointRules {
+    fn default() -> Self {
+        let ladders = [
+            (
+                ItemType::Driver,
+                Rarity::Normal,
+                PointLadder::new(&[400, 600, 648, 704, 760], &[(400, 8)]),
+            ),
+            (
+                ItemType::Driver,
+                Rarity::Super,
+                PointLadder::new(&[450, 675, 765, 870, 975], &[(450, 9), (675, 15)]),
+            ),
+            (
+                ItemType::Driver,
+                Rarity::HighEnd,
+                PointLadder::new(&[500, 800, 980, 1190, 1400], &[(500, 12), (800, 30)]),
+            ),
+            (
+                ItemType::Kart,
+                Rarity::Normal,
+                PointLadder::new(&[200, 300, 324, 352, 380], &[(200, 4)]),
+            ),
+            (
+                ItemType::Kart,
+                Rarity::Super,
+                // this is a sneaky split
+                PointLadder::new(&[220, 330, 366, 408, 450], &[(220, 4), (280, 5), (330, 6)]),
+            ),
+            (
+                ItemType::Kart,
+                Rarity::HighEnd,
+                PointLadder::new(&[250, 400, 490, 595, 700], &[(250, 6), (400, 15)]),
+            ),
+        ];
+        let mut rules = PointRules {
+            ladders: BTreeMap::new(),
+            items: BTreeMap::new(),
+            costs: default_costs(),
+            score_weights: None,
+        };
+        for (i_type, rarity, ladder) in ladders {
+            // gliders have the same points as karts
+            if i_type == ItemType::Kart {
+                rules
+                    .ladders
+                    .insert(ladder_name(ItemType::Glider, rarity), ladder.clone());
+            }
+            rules.ladders.insert(ladder_name(i_type, rarity), ladder);
+        }
+        rules.ladders.insert(
+            MII_LADDER.into(),
+            PointLadder::new(&[500, 840, 1020, 1230, 1440], &[(500, 2), (840, 10)]),
+        );
+        rules
+    }
+}
+
+impl PointRules {
+    pub fn ladder_name(&self, item: &Item) -> String {
+        self.items
+            .get(&item.id)
+            .cloned()
+            .unwrap_or_else(|| ladder_name(item.i_type, item.rarity))
+    }
+
+    pub fn ladder(&self, item: &Item) -> Option<&PointLadder> {
+        self.ladders.get(&self.ladder_name(item))
+    }
+
+    // the costs follow the ladder, the Miis have their own
+    pub fn costs(&self, item: &Item) -> Option<&UpgradeCosts> {
+        self.costs.get(&self.ladder_name(item))
+    }
+
+    pub fn score_weights(&self) -> ScoreWeights {
+        self.score_weights.unwrap_or_default()
+    }
+
+    pub fn merge(&mut self, other: PointRules) {
+        self.ladders.extend(other.ladders);
+        self.items.extend(other.items);
+        self.costs.extend(other.costs);
+        if other.score_weights.is_some() {
+            self.score_weights = other.score_weights;
+        }
+    }
+}
+
+// one table for each default ladder
+fn default_costs() -> BTreeMap<String, UpgradeCosts> {
+    let costs = UpgradeCosts {
+        level_duplicates: vec![1, 1, 2, 2, 3, 3, 4],
+        cap_tickets: vec![1, 1, 1],
+        cap_grand_stars: vec![],
+    };
+    let mut names = vec![MII_LADDER.to_string()];
+    for i_type in [ItemType::Driver, ItemType::Kart, ItemType::Glider] {
+        for rarity in [Rarity::Normal, Rarity::Super, Rarity::HighEnd] {
+            names.push(ladder_name(i_type, rarity));
+        }
+    }
+    names.into_iter().map(|n| (n, costs.clone())).collect()
+}
+
+pub fn ladder_name(i_type: ItemType, rarity: Rarity) -> String {
+    let rarity = match rarity {
+        Rarity::Normal => "normal",
+        Rarity::Super => "super",
+        Rarity::HighEnd => "high_end",
+    };
+    format!("{}_{}", i_type, rarity)
+}
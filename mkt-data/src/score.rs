@@ -0,0 +1,213 @@
+use std::cmp::Reverse;
+
+use itertools::Itertools;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    rules::PointLadder, Item, ItemId, ItemPoints, ItemType, MktData, MktInventory, OwnedItem,
+};
+
+// Where the game shows an item for a course: favorites at the required level are
+// on the top shelf, favored items and favorites below the required level are in
+// the middle, everything else at the bottom.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Shelf {
+    Bottom,
+    Middle,
+    Top,
+}
+
+// Share of the base points kept on each shelf, part of the data rules.
+// The game doesn't publish its formula: the defaults are our own estimates, a top
+// shelf item keeps its base points and each shelf below loses a quarter of them.
+// They live in the data so an update can correct them without a new release.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScoreWeights {
+    pub top: f64,
+    pub middle: f64,
+    pub bottom: f64,
+}
+
+impl Default for ScoreWeights {
+    fn default() -> Self {
+        ScoreWeights {
+            top: 1.0,
+            middle: 0.75,
+            bottom: 0.5,
+        }
+    }
+}
+
+impl ScoreWeights {
+    pub fn weight(&self, shelf: Shelf) -> f64 {
+        match shelf {
+            Shelf::Top => self.top,
+            Shelf::Middle => self.middle,
+            Shelf::Bottom => self.bottom,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemScore {
+    pub id: ItemId,
+    pub i_type: ItemType,
+    pub shelf: Shelf,
+    // owned points, never above the last cap tier
+    pub points: ItemPoints,
+    pub cap_tier: u8,
+    pub score: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadoutScore {
+    pub driver: ItemScore,
+    pub kart: ItemScore,
+    pub glider: ItemScore,
+    pub total: u32,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BestLoadout {
+    // the best pick first, then the alternates
+    pub drivers: Vec<ItemScore>,
+    pub karts: Vec<ItemScore>,
+    pub gliders: Vec<ItemScore>,
+}
+
+impl BestLoadout {
+    pub fn items(&self, i_type: ItemType) -> &[ItemScore] {
+        match i_type {
+            ItemType::Driver => &self.drivers,
+            ItemType::Kart => &self.karts,
+            ItemType::Glider => &self.gliders,
+        }
+    }
+}
+
+pub fn shelf(item: &Item, owned: &OwnedItem, course: &str) -> Shelf {
+    if let Some(c) = item.favorite_courses.iter().find(|c| c.id == course) {
+        if owned.lvl >= c.lvl {
+            Shelf::Top
+        } else {
+            Shelf::Middle
+        }
+    } else if item.favored_courses.iter().any(|c| c.id == course) {
+        Shelf::Middle
+    } else {
+        Shelf::Bottom
+    }
+}
+
+pub fn score_item(
+    item: &Item,
+    ladder: &PointLadder,
+    owned: &OwnedItem,
+    course: &str,
+    weights: &ScoreWeights,
+) -> ItemScore {
+    let shelf = shelf(item, owned, course);
+    let points = owned.points.min(ladder.max_points());
+    ItemScore {
+        id: item.id.clone(),
+        i_type: item.i_type,
+        shelf,
+        points,
+        cap_tier: ladder.cap_tier(points),
+        score: (points as f64 * weights.weight(shelf)).round() as u32,
+    }
+}
+
+// none when an item is not owned, not in the data, or without a point ladder
+pub fn score_loadout(
+    data: &MktData,
+    inv: &MktInventory,
+    course: &str,
+    [driver, kart, glider]: [&str; 3],
+) -> Option<LoadoutScore> {
+    let weights = &data.rules.score_weights();
+    let score = |i_type: ItemType, id: &str| {
+        let item = data.items(i_type).get(id)?;
+        let owned = inv.items(i_type).get(id)?;
+        Some(score_item(
+            item,
+            data.point_ladder(item)?,
+            owned,
+            course,
+            weights,
+        ))
+    };
+    let driver = score(ItemType::Driver, driver)?;
+    let kart = score(ItemType::Kart, kart)?;
+    let glider = score(ItemType::Glider, glider)?;
+    let total = driver.score + kart.score + glider.score;
+    Some(LoadoutScore {
+        driver,
+        kart,
+        glider,
+        total,
+    })
+}
+
+// owned items of a type from the best score to the worst, ties go to the data order
+pub fn rank_items(
+    data: &MktData,
+    inv: &MktInventory,
+    course: &str,
+    i_type: ItemType,
+) -> Vec<ItemScore> {
+    let weights = &data.rules.score_weights();
+    let items = data.items(i_type);
+    inv.items(i_type)
+        .values()
+        .filter_map(|o| {
+            let item = items.get(&o.id)?;
+            Some((item, data.point_ladder(item)?, o))
+        })
+        .sorted_by_key(|(i, _, _)| (i.sort, &i.id))
+        .map(|(i, l, o)| score_item(i, l, o, course, weights))
+        .sorted_by(|a, b| b.score.cmp(&a.score).then(b.shelf.cmp(&a.shelf)))
+        .collect()
+}
+
+// Owned items of a type for a course, the ones meeting the course requirements first,
+// then the most points. Ties go to the data order.
+pub fn best_items(
+    data: &MktData,
+    inv: &MktInventory,
+    course: &str,
+    i_type: ItemType,
+) -> Vec<ItemScore> {
+    let weights = &data.rules.score_weights();
+    let items = data.items(i_type);
+    inv.items(i_type)
+        .values()
+        .filter_map(|o| {
+            let item = items.get(&o.id)?;
+            let score = score_item(item, data.point_ladder(item)?, o, course, weights);
+            Some((item, score))
+        })
+        .sorted_by_key(|(i, s)| (Reverse(s.shelf), Reverse(s.points), i.sort, &i.id))
+        .map(|(_, s)| s)
+        .collect()
+}
+
+// the best owned item of each type, with a few alternates for items already used
+// on another course of the same cup
+pub fn best_loadout(
+    data: &MktData,
+    inv: &MktInventory,
+    course: &str,
+    alternates: usize,
+) -> BestLoadout {
+    let best = |i_type| {
+        let mut items = best_items(data, inv, course, i_type);
+        items.truncate(alternates + 1);
+        items
+    };
+    BestLoadout {
+        drivers: best(ItemType::Driver),
+        karts: best(ItemType::Kart),
+        gliders: best(ItemType::Glider),
+    }
+}
@@ -0,0 +1,386 @@
+use std::{collections::HashMap, fmt::Display};
+
+use itertools::Itertools;
+
+use crate::{
+    course_parts_from_id, item_id_from_name, CourseAvailability, CourseId, ItemId, ItemLvl,
+    ItemRequirement, ItemType, MktData, TourId,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue {
+    // a course requires an item that doesn't exist
+    MissingItem {
+        course: CourseId,
+        item: ItemId,
+    },
+    // an item is available on a course that doesn't exist
+    MissingCourse {
+        item: ItemId,
+        course: CourseId,
+    },
+    // a course lists an item that doesn't list the course back, or the other way around
+    FavoriteMismatch {
+        course: CourseId,
+        item: ItemId,
+        course_lvl: Option<ItemLvl>,
+        item_lvl: Option<ItemLvl>,
+    },
+    // the same, for the favored relations
+    FavoredMismatch {
+        course: CourseId,
+        item: ItemId,
+        course_lvl: Option<ItemLvl>,
+        item_lvl: Option<ItemLvl>,
+    },
+    // the same sort value is used more than once for courses or for an item type
+    DuplicateSort {
+        sort: u32,
+        ids: Vec<String>,
+    },
+    // the map key is not the id of the course or item it holds
+    KeyMismatch {
+        key: String,
+        id: String,
+    },
+    // an item is in the list of another item type
+    WrongItemType {
+        id: ItemId,
+        i_type: ItemType,
+    },
+    // an item id is not generated from its name
+    IdMismatch {
+        id: ItemId,
+        expected: ItemId,
+    },
+    // a course id can't be split in generation, name and type
+    UnparsableCourseId {
+        id: CourseId,
+    },
+    // a tour lists a course or a spotlight item that doesn't exist
+    MissingTourEntry {
+        tour: TourId,
+        id: String,
+    },
+    // a tour ends before it starts
+    InvalidTourDates {
+        tour: TourId,
+    },
+    // an alias doesn't lead to an item, or hides an existing one
+    InvalidAlias {
+        from: ItemId,
+        to: ItemId,
+    },
+    // an item uses a point ladder that isn't in the rules
+    MissingPointLadder {
+        item: ItemId,
+        ladder: String,
+    },
+    // a point ladder without caps or steps, or with unsorted ones
+    InvalidPointLadder {
+        ladder: String,
+    },
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationIssue::MissingItem { course, item } => {
+                write!(f, "course {} requires missing item {}", course, item)
+            }
+            ValidationIssue::MissingCourse { item, course } => {
+                write!(f, "item {} is available on missing course {}", item, course)
+            }
+            ValidationIssue::FavoriteMismatch {
+                course,
+                item,
+                course_lvl,
+                item_lvl,
+            } => write!(
+                f,
+                "course {} and item {} disagree: course lvl {:?}, item lvl {:?}",
+                course, item, course_lvl, item_lvl
+            ),
+            ValidationIssue::FavoredMismatch {
+                course,
+                item,
+                course_lvl,
+                item_lvl,
+            } => write!(
+                f,
+                "course {} and favored item {} disagree: course lvl {:?}, item lvl {:?}",
+                course, item, course_lvl, item_lvl
+            ),
+            ValidationIssue::DuplicateSort { sort, ids } => {
+                write!(f, "sort {} is used by {}", sort, ids.join(", "))
+            }
+            ValidationIssue::KeyMismatch { key, id } => {
+                write!(f, "key {} holds {}", key, id)
+            }
+            ValidationIssue::WrongItemType { id, i_type } => {
+                write!(f, "item {} is a {} in the wrong list", id, i_type)
+            }
+            ValidationIssue::IdMismatch { id, expected } => {
+                write!(f, "item {} should have id {}", id, expected)
+            }
+            ValidationIssue::UnparsableCourseId { id } => {
+                write!(f, "course id {} can't be parsed", id)
+            }
+            ValidationIssue::MissingTourEntry { tour, id } => {
+                write!(f, "tour {} lists missing {}", tour, id)
+            }
+            ValidationIssue::InvalidTourDates { tour } => {
+                write!(f, "tour {} ends before it starts", tour)
+            }
+            ValidationIssue::InvalidAlias { from, to } => {
+                write!(f, "alias {} -> {} is invalid", from, to)
+            }
+            ValidationIssue::MissingPointLadder { item, ladder } => {
+                write!(f, "item {} uses missing point ladder {}", item, ladder)
+            }
+            ValidationIssue::InvalidPointLadder { ladder } => {
+                write!(f, "point ladder {} is invalid", ladder)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ValidationReport {
+    pub issues: Vec<ValidationIssue>,
+}
+
+impl ValidationReport {
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for issue in &self.issues {
+            writeln!(f, "{}", issue)?;
+        }
+        Ok(())
+    }
+}
+
+impl MktData {
+    pub fn validate(&self) -> ValidationReport {
+        let mut issues = vec![];
+
+        // courses
+        for (key, course) in self.courses.iter().sorted_by_key(|(k, _)| *k) {
+            if *key != course.id {
+                issues.push(ValidationIssue::KeyMismatch {
+                    key: key.clone(),
+                    id: course.id.clone(),
+                });
+            }
+            if course_parts_from_id(&course.id).1.is_empty() {
+                issues.push(ValidationIssue::UnparsableCourseId {
+                    id: course.id.clone(),
+                });
+            }
+            for favored in [false, true] {
+                let requirements = match favored {
+                    false => &course.favorite_items,
+                    true => &course.favored_items,
+                };
+                for ItemRequirement { id, lvl } in requirements.iter().sorted() {
+                    if let Some(item) = self.get_item(id) {
+                        let courses = match favored {
+                            false => &item.favorite_courses,
+                            true => &item.favored_courses,
+                        };
+                        if !courses.contains(&(course.id.clone(), *lvl).into()) {
+                            issues.push(mismatch(
+                                favored,
+                                course.id.clone(),
+                                id.clone(),
+                                Some(*lvl),
+                                courses.iter().find(|c| c.id == course.id).map(|c| c.lvl),
+                            ));
+                        }
+                    } else {
+                        issues.push(ValidationIssue::MissingItem {
+                            course: course.id.clone(),
+                            item: id.clone(),
+                        });
+                    }
+                }
+            }
+        }
+        issues.extend(duplicate_sorts(
+            self.courses.values().map(|c| (c.sort, &c.id)),
+        ));
+
+        // items
+        for i_type in [ItemType::Driver, ItemType::Kart, ItemType::Glider] {
+            let items = self.items(i_type);
+            for (key, item) in items.iter().sorted_by_key(|(k, _)| *k) {
+                if *key != item.id {
+                    issues.push(ValidationIssue::KeyMismatch {
+                        key: key.clone(),
+                        id: item.id.clone(),
+                    });
+                }
+                if item.i_type != i_type {
+                    issues.push(ValidationIssue::WrongItemType {
+                        id: item.id.clone(),
+                        i_type: item.i_type,
+                    });
+                }
+                let expected = item_id_from_name(&item.name, item.i_type);
+                if item.id != expected {
+                    issues.push(ValidationIssue::IdMismatch {
+                        id: item.id.clone(),
+                        expected,
+                    });
+                }
+                let ladder = self.rules.ladder_name(item);
+                if !self.rules.ladders.contains_key(&ladder) {
+                    issues.push(ValidationIssue::MissingPointLadder {
+                        item: item.id.clone(),
+                        ladder,
+                    });
+                }
+                for favored in [false, true] {
+                    let courses = match favored {
+                        false => &item.favorite_courses,
+                        true => &item.favored_courses,
+                    };
+                    for CourseAvailability { id, lvl } in courses.iter().sorted() {
+                        if let Some(course) = self.courses.get(id) {
+                            // only report one side, the course loop reports the other
+                            let requirements = match favored {
+                                false => &course.favorite_items,
+                                true => &course.favored_items,
+                            };
+                            let course_lvl =
+                                requirements.iter().find(|r| r.id == item.id).map(|r| r.lvl);
+                            if course_lvl.is_none() {
+                                issues.push(mismatch(
+                                    favored,
+                                    id.clone(),
+                                    item.id.clone(),
+                                    course_lvl,
+                                    Some(*lvl),
+                                ));
+                            }
+                        } else {
+                            issues.push(ValidationIssue::MissingCourse {
+                                item: item.id.clone(),
+                                course: id.clone(),
+                            });
+                        }
+                    }
+                }
+            }
+            issues.extend(duplicate_sorts(items.values().map(|i| (i.sort, &i.id))));
+        }
+
+        // tours
+        for (key, tour) in self.tours.iter().sorted_by_key(|(k, _)| *k) {
+            if *key != tour.id {
+                issues.push(ValidationIssue::KeyMismatch {
+                    key: key.clone(),
+                    id: tour.id.clone(),
+                });
+            }
+            if tour.end < tour.start {
+                issues.push(ValidationIssue::InvalidTourDates {
+                    tour: tour.id.clone(),
+                });
+            }
+            let missing_courses = tour
+                .courses
+                .iter()
+                .filter(|id| !self.courses.contains_key(*id));
+            let missing_items = tour
+                .spotlight_items
+                .iter()
+                .filter(|id| self.get_item(id).is_none());
+            for id in missing_courses.chain(missing_items) {
+                issues.push(ValidationIssue::MissingTourEntry {
+                    tour: tour.id.clone(),
+                    id: id.clone(),
+                });
+            }
+        }
+        issues.extend(duplicate_sorts(
+            self.tours.values().map(|t| (t.sort, &t.id)),
+        ));
+
+        // aliases
+        for (from, to) in self.aliases.iter().sorted() {
+            if self.get_item(from).is_some() || self.resolve_item_id(to).is_none() {
+                issues.push(ValidationIssue::InvalidAlias {
+                    from: from.clone(),
+                    to: to.clone(),
+                });
+            }
+        }
+
+        // point ladders
+        for (name, ladder) in &self.rules.ladders {
+            let sorted_caps = ladder.caps.windows(2).all(|w| w[0] < w[1]);
+            let sorted_steps = ladder.steps.windows(2).all(|w| w[0].from < w[1].from);
+            if ladder.caps.is_empty()
+                || ladder.steps.is_empty()
+                || !sorted_caps
+                || !sorted_steps
+                || ladder.steps.iter().any(|s| s.step == 0)
+            {
+                issues.push(ValidationIssue::InvalidPointLadder {
+                    ladder: name.clone(),
+                });
+            }
+        }
+
+        ValidationReport { issues }
+    }
+}
+
+fn mismatch(
+    favored: bool,
+    course: CourseId,
+    item: ItemId,
+    course_lvl: Option<ItemLvl>,
+    item_lvl: Option<ItemLvl>,
+) -> ValidationIssue {
+    if favored {
+        ValidationIssue::FavoredMismatch {
+            course,
+            item,
+            course_lvl,
+            item_lvl,
+        }
+    } else {
+        ValidationIssue::FavoriteMismatch {
+            course,
+            item,
+            course_lvl,
+            item_lvl,
+        }
+    }
+}
+
+fn duplicate_sorts<'a>(
+    sorts: impl Iterator<Item = (Option<u32>, &'a String)>,
+) -> impl Iterator<Item = ValidationIssue> {
+    let mut by_sort: HashMap<u32, Vec<String>> = HashMap::new();
+    for (sort, id) in sorts {
+        if let Some(sort) = sort {
+            by_sort.entry(sort).or_default().push(id.clone());
+        }
+    }
+    by_sort
+        .into_iter()
+        .filter(|(_, ids)| ids.len() > 1)
+        .sorted()
+        .map(|(sort, ids)| ValidationIssue::DuplicateSort {
+            sort,
+            ids: ids.into_iter().sorted().collect(),
+        })
+}
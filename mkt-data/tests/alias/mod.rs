@@ -0,0 +1,119 @@
+use mkt_data::validation::*;
+use mkt_data::*;
+
+use crate::{get_test_data, get_test_inventory};
+
+// d_one was renamed d_uno, then d_1
+fn get_alias_data() -> MktData {
+    let mut data = get_test_data();
+    data.aliases.insert("d_one".into(), "d_uno".into());
+    data.aliases.insert("d_uno".into(), "d_1".into());
+    data.aliases.insert("k_old".into(), "k_2".into());
+    data
+}
+
+#[test]
+fn resolve_item_id() {
+    let mut data = get_alias_data();
+    assert_eq!(data.resolve_item_id("d_1").as_deref(), Some("d_1"));
+    assert_eq!(data.resolve_item_id("d_one").as_deref(), Some("d_1"));
+    assert_eq!(data.resolve_item_id("d_x"), None);
+
+    // cycles don't resolve
+    data.aliases.insert("d_a".into(), "d_b".into());
+    data.aliases.insert("d_b".into(), "d_a".into());
+    assert_eq!(data.resolve_item_id("d_a"), None);
+}
+
+#[test]
+fn resolve_inventory_items() {
+    let data = get_alias_data();
+    let mut inv = get_test_inventory(&[("d_one", 3), ("d_1", 1), ("k_old", 2), ("g_x", 1)]);
+
+    let resolved = inv.resolve_items(&data);
+    assert_eq!(
+        resolved.remapped,
+        vec![
+            ("d_one".to_string(), "d_1".to_string()),
+            ("k_old".to_string(), "k_2".to_string())
+        ]
+    );
+    assert_eq!(resolved.unresolved, vec!["g_x".to_string()]);
+
+    // the best level is kept, unknown items are not lost
+    assert_eq!(inv.drivers.len(), 1);
+    assert_eq!(inv.drivers["d_1"].lvl, 3);
+    assert_eq!(inv.karts["k_2"].id, "k_2");
+    assert!(inv.gliders.contains_key("g_x"));
+
+    assert!(inv.resolve_items(&data).remapped.is_empty());
+}
+
+#[test]
+fn from_items_with_aliases() {
+    let data = get_alias_data();
+    let (inv, resolved) = MktInventory::from_items(
+        vec![
+            OwnedItem::new("d_uno".into(), 2, 0),
+            OwnedItem::new("g_x".into(), 1, 0),
+        ],
+        &data,
+    );
+    assert_eq!(inv.drivers["d_1"].lvl, 2);
+    assert!(inv.gliders.is_empty());
+    assert_eq!(
+        resolved.remapped,
+        vec![("d_uno".to_string(), "d_1".to_string())]
+    );
+    assert_eq!(resolved.unresolved, vec!["g_x".to_string()]);
+}
+
+#[test]
+fn from_json_with_aliases() {
+    let data = get_alias_data();
+    let json = get_test_inventory(&[("d_one", 3), ("g_x", 1)])
+        .to_json()
+        .unwrap();
+    let (inv, resolved) = MktInventory::from_json_with_data(&json, &data).unwrap();
+    assert_eq!(inv.drivers["d_1"].lvl, 3);
+    assert_eq!(resolved.unresolved, vec!["g_x".to_string()]);
+}
+
+#[test]
+fn resolve_hashes() {
+    let mut data = get_alias_data();
+    let mut hashes: MktItemHashes = vec![
+        ("d_one".to_string(), "h1".to_string()),
+        ("d_1".to_string(), "h2".to_string()),
+    ]
+    .into_iter()
+    .collect();
+
+    data.merge_hashes(&hashes);
+    assert_eq!(data.drivers["d_1"].hashes.len(), 2);
+
+    let resolved = hashes.resolve_items(&data);
+    assert_eq!(resolved.remapped.len(), 1);
+    assert_eq!(hashes.hashes.len(), 1);
+    assert_eq!(hashes.hashes["d_1"].len(), 2);
+}
+
+#[test]
+fn invalid_aliases() {
+    let mut data = get_alias_data();
+    data.aliases.insert("d_2".into(), "d_1".into());
+    data.aliases.insert("g_old".into(), "g_x".into());
+    assert_eq!(
+        data.validate().issues,
+        vec![
+            ValidationIssue::InvalidAlias {
+                from: "d_2".into(),
+                to: "d_1".into(),
+            },
+            ValidationIssue::InvalidAlias {
+                from: "g_old".into(),
+                to: "g_x".into(),
+            },
+        ]
+    );
+}
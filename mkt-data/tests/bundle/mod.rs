@@ -0,0 +1,40 @@
+use mkt_data::error::MktDataError;
+use mkt_data::*;
+
+use crate::get_test_data;
+
+#[test]
+fn data_round_trip() {
+    let data = get_test_data();
+    let bundle = data.to_bundle().unwrap();
+    assert!(bundle.len() < data.to_json().unwrap().len());
+
+    let from_bundle = MktData::from_bundle(&bundle).unwrap();
+    assert_eq!(from_bundle.to_json().unwrap(), data.to_json().unwrap());
+
+    // already decompressed on the way
+    let json = data.to_json().unwrap();
+    let from_json = MktData::from_bundle(json.as_bytes()).unwrap();
+    assert_eq!(from_json.to_json().unwrap(), json);
+}
+
+#[test]
+fn hashes_round_trip() {
+    let hashes = MktItemHashes::from_json(r#"{"hashes":{"d_1":["abc","def"]}}"#).unwrap();
+    let from_bundle = MktItemHashes::from_bundle(&hashes.to_bundle().unwrap()).unwrap();
+    assert_eq!(from_bundle.version, hashes.version);
+    assert_eq!(from_bundle.hashes, hashes.hashes);
+}
+
+#[test]
+fn invalid_bundle() {
+    let mut bundle = get_test_data().to_bundle().unwrap();
+    bundle.truncate(bundle.len() / 2);
+    assert!(matches!(
+        MktData::from_bundle(&bundle),
+        Err(MktDataError::Bundle { .. })
+    ));
+
+    let e = MktData::load_bundle("tests/bundle/missing.json.gz").unwrap_err();
+    assert!(e.is_not_found());
+}
@@ -0,0 +1,218 @@
+use mkt_data::costs::*;
+use mkt_data::planner::plan_coverage;
+use mkt_data::rules::{UpgradeCosts, MII_LADDER};
+use mkt_data::*;
+
+use crate::{get_test_data, get_test_inventory};
+
+// made-up tables, easier to check than the defaults
+fn get_cost_data() -> MktData {
+    let mut data = get_test_data();
+    data.rules.costs.clear();
+    let costs =
+        |level_duplicates: &[u32], cap_tickets: &[u32], cap_grand_stars: &[u32]| UpgradeCosts {
+            level_duplicates: level_duplicates.to_vec(),
+            cap_tickets: cap_tickets.to_vec(),
+            cap_grand_stars: cap_grand_stars.to_vec(),
+        };
+    let normal = costs(&[1, 2, 2, 3, 4, 5, 6], &[1, 1, 1], &[]);
+    for ladder in ["driver_normal", "kart_normal", "glider_normal"] {
+        data.rules.costs.insert(ladder.into(), normal.clone());
+    }
+    data.rules.costs.insert(
+        "driver_high_end".into(),
+        costs(&[1, 1, 1, 2, 2, 3, 4], &[1, 2, 2], &[0, 0, 1]),
+    );
+    data.rules
+        .costs
+        .insert(MII_LADDER.into(), costs(&[2; 7], &[1, 1, 1], &[]));
+    data
+}
+
+#[test]
+fn level_cost() {
+    let mut data = get_cost_data();
+    let d_1 = data.drivers["d_1"].clone();
+    let cost = |data: &MktData, item: &Item, lvl, target| {
+        costs::level_cost(data, item, lvl, target).map(|c| c.duplicates)
+    };
+    assert_eq!(cost(&data, &d_1, Some(1), 3), Some(3));
+    assert_eq!(cost(&data, &d_1, Some(3), 3), Some(0));
+    // a new item needs its first copy
+    assert_eq!(cost(&data, &d_1, None, 1), Some(1));
+    assert_eq!(cost(&data, &d_1, None, 2), Some(2));
+    assert_eq!(cost(&data, &d_1, Some(1), 8), Some(23));
+
+    let mut high_end = d_1.clone();
+    high_end.rarity = Rarity::HighEnd;
+    assert_eq!(cost(&data, &high_end, Some(1), 8), Some(14));
+    // no costs for that ladder in the data
+    high_end.rarity = Rarity::Super;
+    assert_eq!(cost(&data, &high_end, Some(1), 8), None);
+    assert_eq!(cost(&data, &d_1, Some(1), 9), None);
+
+    // the Miis have their own ladder
+    data.rules.items.insert("d_1".into(), MII_LADDER.into());
+    assert_eq!(cost(&data, &d_1, Some(1), 3), Some(4));
+}
+
+#[test]
+fn cap_cost() {
+    let data = get_cost_data();
+    let mut item = data.drivers["d_1"].clone();
+    let ladder = data.point_ladder(&item).unwrap().clone();
+    let owned = OwnedItem::new("d_1".into(), 1, 620);
+    assert_eq!(owned.point_cap_tier(&ladder), 1);
+    assert_eq!(
+        costs::cap_cost(&data, &item, &owned, 3),
+        Some(Cost {
+            cap_tickets: 2,
+            ..Cost::default()
+        })
+    );
+    assert_eq!(
+        costs::cap_cost(&data, &item, &owned, 1),
+        Some(Cost::default())
+    );
+    // past the last tier of the ladder
+    assert_eq!(costs::cap_cost(&data, &item, &owned, 4), None);
+
+    item.rarity = Rarity::HighEnd;
+    let owned = OwnedItem::new("d_1".into(), 1, 0);
+    assert_eq!(
+        costs::cap_cost(&data, &item, &owned, 3),
+        Some(Cost {
+            cap_tickets: 5,
+            grand_stars: 1,
+            ..Cost::default()
+        })
+    );
+}
+
+#[test]
+fn level_tickets() {
+    let data = get_cost_data();
+    let d_1 = &data.drivers["d_1"];
+    // the tickets replace the most expensive levels
+    assert_eq!(
+        level_cost_using_tickets(&data, d_1, Some(1), 8, 2),
+        Some(Cost {
+            duplicates: 12,
+            level_tickets: 2,
+            ..Cost::default()
+        })
+    );
+    assert_eq!(
+        level_cost_using_tickets(&data, d_1, Some(1), 8, 9),
+        Some(Cost {
+            level_tickets: 7,
+            ..Cost::default()
+        })
+    );
+    // the first copy can't be replaced
+    assert_eq!(
+        level_cost_using_tickets(&data, d_1, None, 3, 5),
+        Some(Cost {
+            duplicates: 1,
+            level_tickets: 2,
+            ..Cost::default()
+        })
+    );
+}
+
+#[test]
+fn plan_cost() {
+    let data = get_cost_data();
+    let mut inv = get_test_inventory(&[("d_1", 2), ("k_1", 1)]);
+    inv.karts.get_mut("k_1").unwrap().points = 100;
+    let upgrades = [
+        Upgrade {
+            id: "d_1".into(),
+            lvl: Some(4),
+            cap_tier: None,
+        },
+        Upgrade {
+            id: "k_1".into(),
+            lvl: Some(2),
+            cap_tier: Some(2),
+        },
+        // not owned, the first copy counts
+        Upgrade {
+            id: "g_1".into(),
+            lvl: Some(2),
+            cap_tier: Some(1),
+        },
+        // not in the data
+        Upgrade {
+            id: "g_9".into(),
+            lvl: Some(8),
+            cap_tier: None,
+        },
+    ];
+    assert_eq!(
+        costs::plan_cost(&data, &inv, &upgrades),
+        (
+            Cost {
+                duplicates: 4 + 1 + 2,
+                cap_tickets: 2 + 1,
+                ..Cost::default()
+            },
+            vec!["g_9".to_string()]
+        )
+    );
+}
+
+#[test]
+fn coverage_plan_cost() {
+    let data = get_cost_data();
+    let inv = get_test_inventory(&[("d_2", 1)]);
+    let plan = plan_coverage(&data, &inv);
+    let expected = plan
+        .items
+        .iter()
+        .map(|p| costs::level_cost(&data, data.get_item(&p.id).unwrap(), p.owned_lvl, p.lvl))
+        .sum::<Option<Cost>>()
+        .unwrap();
+    assert_eq!(costs::coverage_plan_cost(&data, &plan), (expected, vec![]));
+    assert_eq!(
+        costs::coverage_plan_cost(&data, &Default::default()),
+        (Cost::default(), vec![])
+    );
+
+    // without costs in the data
+    let mut data = get_test_data();
+    data.rules.costs.clear();
+    let (_, unknown) = costs::coverage_plan_cost(&data, &plan);
+    assert_eq!(unknown.len(), plan.items.len());
+}
+
+#[test]
+fn default_costs() {
+    // every item of the default ladders has costs
+    let data = get_test_data();
+    let inv = get_test_inventory(&[("d_1", 2), ("k_1", 1)]);
+    for item in data
+        .drivers
+        .values()
+        .chain(data.karts.values())
+        .chain(data.gliders.values())
+    {
+        assert!(
+            costs::level_cost(&data, item, None, 8).is_some(),
+            "{}",
+            item.id
+        );
+    }
+    let d_1 = &data.drivers["d_1"];
+    assert_eq!(
+        costs::level_cost(&data, d_1, Some(2), 8).map(|c| c.duplicates),
+        Some(15)
+    );
+    let owned = OwnedItem::new("d_1".into(), 2, 0);
+    assert_eq!(
+        costs::cap_cost(&data, d_1, &owned, 3).map(|c| c.cap_tickets),
+        Some(3)
+    );
+    let plan = plan_coverage(&data, &inv);
+    assert!(costs::coverage_plan_cost(&data, &plan).1.is_empty());
+}
@@ -0,0 +1,158 @@
+use mkt_data::coverage::*;
+use mkt_data::*;
+
+use crate::{get_test_data, get_test_inventory};
+
+fn add_favored(data: &mut MktData, course: &str, item: &str) {
+    data.courses
+        .get_mut(course)
+        .unwrap()
+        .favored_items
+        .insert((item.to_string(), 0).into());
+    data.get_item_mut(item)
+        .unwrap()
+        .favored_courses
+        .insert((course.to_string(), 0).into());
+}
+
+#[test]
+fn coverage_levels() {
+    let mut data = get_test_data();
+    add_favored(&mut data, "c_d", "d_3");
+    add_favored(&mut data, "c_a", "d_3");
+
+    let inv = get_test_inventory(&[("d_1", 1), ("d_3", 1), ("k_2", 1)]);
+    let level = |course: &str, i_type| data.courses[course].coverage_level(&inv, i_type);
+    assert_eq!(level("c_a", ItemType::Driver), CoverageLevel::Favorite);
+    assert_eq!(level("c_d", ItemType::Driver), CoverageLevel::Favored);
+    assert_eq!(level("c_d", ItemType::Kart), CoverageLevel::Favorite);
+    assert_eq!(level("c_d", ItemType::Glider), CoverageLevel::Uncovered);
+
+    let inv = get_test_inventory(&[("d_3", 1), ("k_2", 1), ("g_2", 1)]);
+    assert_eq!(
+        data.courses["c_d"].overall_coverage_level(&inv),
+        CoverageLevel::Favored
+    );
+    assert_eq!(
+        data.courses["c_b"].overall_coverage_level(&inv),
+        CoverageLevel::Uncovered
+    );
+}
+
+#[test]
+fn favored_round_trip() {
+    let mut data = get_test_data();
+    add_favored(&mut data, "c_d", "d_3");
+    let json = data.to_json().unwrap();
+    let data = MktData::from_json(&json).unwrap();
+    assert!(data.courses["c_d"]
+        .favored_items
+        .contains(&("d_3".to_string(), 0).into()));
+    assert!(data.drivers["d_3"]
+        .favored_courses
+        .contains(&("c_d".to_string(), 0).into()));
+    // empty sets are left out
+    assert_eq!(json.matches("favored_items").count(), 1);
+}
+
+#[test]
+fn coverage_report() {
+    let data = get_test_data();
+    let inv = get_test_inventory(&[("d_1", 1), ("k_1", 1), ("g_2", 1)]);
+    let report = CoverageReport::new(&data, &inv);
+
+    let c_a = &report.courses["c_a"];
+    assert_eq!((c_a.count(None), c_a.owned_count(None)), (4, 2));
+    assert_eq!(c_a.driver_owned_count, 1);
+    assert_eq!(
+        c_a.coverage(Some(ItemType::Driver)),
+        CoverageLevel::Favorite
+    );
+    assert_eq!(c_a.coverage(None), CoverageLevel::Uncovered);
+    assert_eq!(
+        report.courses["c_b"].coverage(None),
+        CoverageLevel::Favorite
+    );
+
+    assert_eq!(
+        report.items["d_1"],
+        ItemStats {
+            fav_course_count: 2,
+            max_fav_course_count: 2,
+            add_course_count: 0,
+            max_add_course_count: 0,
+        }
+    );
+    // c_a is already covered by d_1, only c_c would be added
+    assert_eq!(
+        report.items["d_2"],
+        ItemStats {
+            fav_course_count: 0,
+            max_fav_course_count: 2,
+            add_course_count: 1,
+            max_add_course_count: 1,
+        }
+    );
+
+    assert_eq!(
+        report.course_totals(),
+        CourseGroupStats {
+            course_count: 4,
+            covered_count: 1,
+            favored_count: 0,
+        }
+    );
+    assert_eq!(
+        report.generations.keys().collect::<Vec<_>>(),
+        vec![&CourseGeneration::New]
+    );
+    assert_eq!(
+        report.rarities[&(ItemType::Driver, Rarity::Normal)],
+        ItemGroupStats {
+            item_count: 3,
+            owned_count: 1,
+        }
+    );
+    assert_eq!(report.item_totals(ItemType::Kart).owned_count, 1);
+}
+
+#[test]
+fn incremental_update_matches_full_report() {
+    let mut data = get_test_data();
+    add_favored(&mut data, "c_d", "d_3");
+    let mut inv = get_test_inventory(&[("d_1", 1), ("k_1", 1), ("g_2", 1)]);
+    let mut report = CoverageReport::new(&data, &inv);
+
+    let edits: &[(&str, Option<ItemLvl>)] = &[
+        ("d_2", Some(1)),
+        ("d_2", Some(3)),
+        ("d_1", None),
+        ("d_3", Some(2)),
+        ("k_2", Some(1)),
+        ("g_1", Some(1)),
+        ("k_1", None),
+        ("g_2", None),
+        // not in the data
+        ("d_9", Some(1)),
+    ];
+    for (id, lvl) in edits {
+        match lvl {
+            Some(lvl) => inv.update_inventory(MktInventory::from_item(
+                item_type_from_id(id).unwrap(),
+                OwnedItem::new(id.to_string(), *lvl, 0),
+            )),
+            None => {
+                inv.drivers.remove(*id);
+                inv.karts.remove(*id);
+                inv.gliders.remove(*id);
+            }
+        }
+        let update = report.update_item(&data, &inv, id);
+        assert_eq!(report, CoverageReport::new(&data, &inv), "after {}", id);
+        if data.get_item(id).is_some() {
+            assert!(update.items.contains(&id.to_string()));
+        } else {
+            assert_eq!(update, CoverageUpdate::default());
+        }
+    }
+}
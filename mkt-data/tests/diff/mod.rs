@@ -0,0 +1,138 @@
+use mkt_data::{diff::*, *};
+
+use crate::{add_favorite, get_test_data, get_test_inventory};
+
+fn get_diff() -> InventoryDiff {
+    let old = get_test_inventory(&[("d_1", 1), ("d_2", 2), ("k_1", 1)]);
+    let mut new = get_test_inventory(&[("d_1", 1), ("d_2", 3), ("g_1", 1)]);
+    new.drivers.get_mut("d_1").unwrap().points = 620;
+    old.diff(&new)
+}
+
+#[test]
+fn diff_items() {
+    let diff = get_diff();
+    assert_eq!(
+        diff.items.iter().map(|i| i.id.as_str()).collect::<Vec<_>>(),
+        vec!["d_1", "d_2", "k_1", "g_1"]
+    );
+    assert_eq!(
+        diff.added().map(|i| i.id.as_str()).collect::<Vec<_>>(),
+        vec!["g_1"]
+    );
+    assert_eq!(
+        diff.removed().map(|i| i.id.as_str()).collect::<Vec<_>>(),
+        vec!["k_1"]
+    );
+
+    let d_1 = &diff.items[0];
+    assert_eq!(d_1.lvl_change(), None);
+    assert_eq!(d_1.points_change(), Some((0, 620)));
+    assert_eq!(d_1.cap_tier_change(&get_test_data()), Some((0, 1)));
+    let d_2 = &diff.items[1];
+    assert_eq!(d_2.lvl_change(), Some((2, 3)));
+    assert_eq!(d_2.points_change(), None);
+}
+
+#[test]
+fn diff_same_inventory() {
+    let inv = get_test_inventory(&[("d_1", 1), ("k_1", 1)]);
+    let mut other = inv.clone();
+    other.drivers.get_mut("d_1").unwrap().last_changed = None;
+    assert!(inv.diff(&other).is_empty());
+}
+
+#[test]
+fn diff_text() {
+    let text = get_diff().to_text(&get_test_data());
+    assert_eq!(
+        text,
+        "Drivers\n\
+         ~ 1: 0 -> 620 pts, cap tier 0 -> 1\n\
+         ~ 2: lvl 2 -> 3\n\
+         Karts\n\
+         - 1: lvl 1, 0 pts\n\
+         Gliders\n\
+         + 1: lvl 1, 0 pts\n"
+    );
+}
+
+#[test]
+fn diff_markdown() {
+    let markdown = get_diff().to_markdown(&get_test_data());
+    assert!(markdown.starts_with("### Drivers\n\n| Item | Change | Details |\n"));
+    assert!(markdown.contains("| 1 | removed | lvl 1, 0 pts |\n"));
+    assert!(markdown.contains("### Gliders\n"));
+}
+
+// d_3 removed, k_3 added, g_1 rarity, and the coverage of c_a changed
+fn get_data_diff() -> (MktData, MktData) {
+    let old = get_test_data();
+    let mut new = get_test_data();
+    new.drivers.remove("d_3");
+    new.courses
+        .get_mut("c_c")
+        .unwrap()
+        .favorite_items
+        .retain(|r| r.id != "d_3");
+    let kart = Item::new(ItemType::Kart, Rarity::Super, "3".into(), Some(8));
+    new.karts.insert(kart.id.clone(), kart);
+    new.gliders.get_mut("g_1").unwrap().rarity = Rarity::HighEnd;
+    new.courses.get_mut("c_b").unwrap().sort = Some(5);
+    let c_a = new.courses.get_mut("c_a").unwrap();
+    c_a.favorite_items.retain(|r| r.id != "d_2");
+    add_favorite(&mut new, "c_a", "d_2", 6);
+    add_favorite(&mut new, "c_a", "k_3", 1);
+    (old, new)
+}
+
+#[test]
+fn data_diff() {
+    let (old, new) = get_data_diff();
+    let diff = old.diff(&new);
+    assert_eq!(diff.added_items, vec!["k_3"]);
+    assert_eq!(diff.removed_items, vec!["d_3"]);
+    assert_eq!(
+        diff.rarity_changes,
+        vec![("g_1".to_string(), Rarity::Normal, Rarity::HighEnd)]
+    );
+    assert_eq!(
+        diff.sort_changes,
+        vec![SortChange {
+            id: "c_b".into(),
+            old: Some(2),
+            new: Some(5),
+        }]
+    );
+    assert_eq!(
+        diff.coverage_changes
+            .iter()
+            .map(|c| (c.course.as_str(), c.added.len(), c.removed.len()))
+            .collect::<Vec<_>>(),
+        vec![("c_a", 2, 1), ("c_c", 0, 1)]
+    );
+    assert!(old.diff(&old).is_empty());
+}
+
+#[test]
+fn data_diff_markdown() {
+    let (old, new) = get_data_diff();
+    let markdown = old.diff(&new).to_markdown(&old, &new);
+    assert_eq!(
+        markdown,
+        "### Added items\n\n\
+         - 3 (`k_3`)\n\n\
+         ### Removed items\n\n\
+         - 3 (`d_3`)\n\n\
+         ### Rarity changes\n\n\
+         - 1: Normal -> HighEnd\n\n\
+         ### Sort changes\n\n\
+         - B: 2 -> 5\n\n\
+         ### Coverage of A\n\n\
+         - + 2 (lvl 6)\n\
+         - + 3 (lvl 1)\n\
+         - - 2 (lvl 3)\n\n\
+         ### Coverage of C\n\n\
+         - - 3 (lvl 1)\n\n"
+    );
+}
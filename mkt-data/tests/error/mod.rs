@@ -0,0 +1,38 @@
+use mkt_data::error::MktDataError;
+use mkt_data::*;
+
+#[test]
+fn missing_file() {
+    let error = MktData::load("does/not/exist.json").unwrap_err();
+    assert!(error.is_not_found());
+    assert_eq!(error.path(), Some("does/not/exist.json"));
+}
+
+#[test]
+fn bad_json() {
+    match MktInventory::from_json("{\n  \"drivers\": {,\n}") {
+        Err(MktDataError::Json { line, column, .. }) => assert_eq!((line, column), (2, 15)),
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn schema_mismatch() {
+    let json = r#"{"drivers":{"d_mario":{"id":"d_mario","lvl":"high","points":500}},"karts":{},"gliders":{}}"#;
+    match MktInventory::from_json(json) {
+        Err(MktDataError::Schema { at, id, .. }) => {
+            assert_eq!(at, "drivers.d_mario.lvl");
+            assert_eq!(id.as_deref(), Some("d_mario"));
+        }
+        other => panic!("unexpected {:?}", other),
+    }
+}
+
+#[test]
+fn unsupported_version() {
+    let json = r#"{"version":999,"hashes":{}}"#;
+    match MktItemHashes::from_json(json) {
+        Err(MktDataError::UnsupportedVersion { version, .. }) => assert_eq!(version, 999),
+        other => panic!("unexpected {:?}", other),
+    }
+}
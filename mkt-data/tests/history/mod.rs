@@ -0,0 +1,128 @@
+use chrono::{DateTime, TimeZone, Utc};
+use mkt_data::history::*;
+
+use crate::{get_test_data, get_test_inventory};
+
+fn day(d: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2022, 1, d, 0, 0, 0).unwrap()
+}
+
+fn get_test_history() -> MktInventoryHistory {
+    let mut history = MktInventoryHistory::new();
+    assert!(history.record(&get_test_inventory(&[("d_1", 1)]), day(1)));
+    assert!(history.record(&get_test_inventory(&[("d_1", 1), ("k_1", 1)]), day(10)));
+    assert!(history.record(
+        &get_test_inventory(&[("d_1", 2), ("d_2", 3), ("k_1", 1)]),
+        day(20)
+    ));
+    // out of order
+    assert!(history.record(&get_test_inventory(&[("d_1", 1), ("g_2", 1)]), day(5)));
+    history
+}
+
+#[test]
+fn record_changes_only() {
+    let mut history = get_test_history();
+    assert_eq!(history.changes.len(), 6);
+    assert!(!history.record(
+        &get_test_inventory(&[("d_1", 2), ("d_2", 3), ("k_1", 1)]),
+        day(25)
+    ));
+    assert_eq!(history.changes.len(), 6);
+    assert!(history.changes.windows(2).all(|w| w[0].date <= w[1].date));
+    // the glider recorded out of order is removed again at the next change
+    assert_eq!(
+        history.changes[3],
+        HistoryChange {
+            date: day(10),
+            id: "g_2".into(),
+            lvl: 0,
+            points: 0,
+        }
+    );
+}
+
+#[test]
+fn record_same_day() {
+    let mut history = get_test_history();
+    let later = day(20) + chrono::Duration::hours(2);
+    assert!(history.record(
+        &get_test_inventory(&[("d_1", 3), ("d_2", 3), ("k_1", 1)]),
+        later
+    ));
+    assert_eq!(history.changes.len(), 6);
+    assert_eq!(history.at(later).unwrap().drivers["d_1"].lvl, 3);
+
+    // back to the previous day, the change is undone
+    let later = later + chrono::Duration::hours(2);
+    assert!(history.record(
+        &get_test_inventory(&[("d_1", 1), ("d_2", 3), ("k_1", 1)]),
+        later
+    ));
+    assert_eq!(history.changes.len(), 5);
+    assert_eq!(history.level_timeline("d_1"), vec![(day(1), 1)]);
+}
+
+#[test]
+fn compact_history() {
+    let mut history = get_test_history();
+    let before = history.at(day(31)).unwrap();
+    history.compact(day(15));
+    assert_eq!(history.changes.len(), 4);
+    assert!(!history.at(day(9)).unwrap().gliders.contains_key("g_2"));
+    assert!(history.at(day(31)).unwrap().diff(&before).is_empty());
+    assert!(!history.at(day(15)).unwrap().gliders.contains_key("g_2"));
+}
+
+#[test]
+fn inventory_at_date() {
+    let history = get_test_history();
+    assert!(history.at(day(1) - chrono::Duration::days(1)).is_none());
+    assert_eq!(history.at(day(1)).unwrap().drivers.len(), 1);
+    assert!(history.at(day(7)).unwrap().gliders.contains_key("g_2"));
+    assert_eq!(history.at(day(31)).unwrap().drivers["d_1"].lvl, 2);
+}
+
+#[test]
+fn progress_at_date() {
+    let data = get_test_data();
+    let history = get_test_history();
+
+    let progress = history.progress_at(&data, day(12)).unwrap();
+    assert_eq!(progress.owned_drivers, 1);
+    assert_eq!(progress.owned_karts, 1);
+    assert_eq!(progress.owned_gliders, 0);
+    assert_eq!(progress.covered_drivers, 2);
+    assert_eq!(progress.covered_karts, 3);
+    assert_eq!(progress.covered_gliders, 0);
+
+    let progress = history.progress(&data);
+    assert_eq!(progress.len(), 4);
+    assert_eq!(
+        progress
+            .iter()
+            .map(|p| p.covered_drivers)
+            .collect::<Vec<_>>(),
+        vec![2, 2, 2, 3]
+    );
+}
+
+#[test]
+fn level_timeline() {
+    let history = get_test_history();
+    assert_eq!(
+        history.level_timeline("d_1"),
+        vec![(day(1), 1), (day(20), 2)]
+    );
+    assert_eq!(history.level_timeline("d_2"), vec![(day(20), 3)]);
+    assert!(history.level_timeline("d_3").is_empty());
+}
+
+#[test]
+fn history_serialization() {
+    let history = get_test_history();
+    let history = MktInventoryHistory::from_json(&history.to_json().unwrap()).unwrap();
+    assert_eq!(history.changes.len(), 6);
+    assert_eq!(history.changes[5].date, day(20));
+}
+
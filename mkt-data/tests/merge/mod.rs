@@ -0,0 +1,111 @@
+use chrono::{DateTime, TimeZone, Utc};
+use mkt_data::{item_type_from_id, merge::*, ItemLvl, ItemType, MktInventory};
+
+use crate::get_test_inventory;
+
+fn day(d: u32) -> DateTime<Utc> {
+    Utc.with_ymd_and_hms(2022, 1, d, 0, 0, 0).unwrap()
+}
+
+// all the items added on the first day, then changed on that day
+fn get_dated_inventory(items: &[(&str, ItemLvl, u32)]) -> MktInventory {
+    let mut inv = get_test_inventory(
+        &items
+            .iter()
+            .map(|(id, lvl, _)| (*id, *lvl))
+            .collect::<Vec<_>>(),
+    );
+    for (id, _, d) in items {
+        let i_type = item_type_from_id(id).unwrap();
+        let item = match i_type {
+            ItemType::Driver => inv.drivers.get_mut(*id),
+            ItemType::Kart => inv.karts.get_mut(*id),
+            ItemType::Glider => inv.gliders.get_mut(*id),
+        }
+        .unwrap();
+        item.added = Some(day(1));
+        item.last_changed = Some(day(*d));
+    }
+    inv
+}
+
+fn lvl(inv: &MktInventory, id: &str) -> Option<ItemLvl> {
+    inv.get_item(id).map(|i| i.lvl)
+}
+
+#[test]
+fn merge_changes_from_both_sides() {
+    let base = get_dated_inventory(&[("d_1", 1, 1), ("d_2", 1, 1), ("k_1", 1, 1)]);
+    let local = get_dated_inventory(&[("d_1", 2, 5), ("d_2", 1, 1), ("k_1", 1, 1)]);
+    let incoming = get_dated_inventory(&[("d_1", 1, 1), ("d_2", 3, 6), ("k_2", 1, 6)]);
+
+    let merge = local.merge_three_way(Some(&base), &incoming);
+    assert!(merge.conflicts.is_empty());
+    assert_eq!(lvl(&merge.inventory, "d_1"), Some(2));
+    assert_eq!(lvl(&merge.inventory, "d_2"), Some(3));
+    // removed on the other device
+    assert_eq!(lvl(&merge.inventory, "k_1"), None);
+    assert_eq!(lvl(&merge.inventory, "k_2"), Some(1));
+}
+
+#[test]
+fn merge_conflicts_by_date() {
+    let base = get_dated_inventory(&[("d_1", 1, 1), ("d_2", 1, 1)]);
+    let local = get_dated_inventory(&[("d_1", 2, 5), ("d_2", 2, 8)]);
+    let incoming = get_dated_inventory(&[("d_1", 3, 7), ("d_2", 3, 6)]);
+
+    let merge = local.merge_three_way(Some(&base), &incoming);
+    assert_eq!(lvl(&merge.inventory, "d_1"), Some(3));
+    assert_eq!(lvl(&merge.inventory, "d_2"), Some(2));
+    assert_eq!(
+        merge
+            .conflicts
+            .iter()
+            .map(|c| (c.id.as_str(), c.kept))
+            .collect::<Vec<_>>(),
+        vec![("d_1", MergeSide::Incoming), ("d_2", MergeSide::Local)]
+    );
+}
+
+#[test]
+fn merge_edit_wins_over_removal() {
+    let base = get_dated_inventory(&[("d_1", 1, 1), ("k_1", 1, 1)]);
+    let local = get_dated_inventory(&[("d_1", 2, 5)]);
+    let incoming = get_dated_inventory(&[("d_1", 1, 1), ("k_1", 2, 6)]);
+
+    let merge = local.merge_three_way(Some(&base), &incoming);
+    assert_eq!(lvl(&merge.inventory, "k_1"), Some(2));
+    assert_eq!(merge.conflicts.len(), 1);
+    assert_eq!(merge.conflicts[0].kept, MergeSide::Incoming);
+    assert!(merge.conflicts[0].local.is_none());
+}
+
+#[test]
+fn merge_old_backup() {
+    // the backup was made before the base, and before k_2 was added
+    let backup = get_dated_inventory(&[("d_1", 1, 2), ("k_1", 1, 2)]);
+    let base = get_dated_inventory(&[("d_1", 2, 5), ("k_1", 1, 2)]);
+    let mut local = get_dated_inventory(&[("d_1", 3, 8), ("k_1", 1, 2), ("k_2", 1, 9)]);
+    local.karts.get_mut("k_2").unwrap().added = Some(day(9));
+    let mut base = base;
+    base.karts.insert("k_2".into(), local.karts["k_2"].clone());
+
+    let merge = local.merge_three_way(Some(&base), &backup);
+    assert!(merge.conflicts.is_empty());
+    assert_eq!(lvl(&merge.inventory, "d_1"), Some(3));
+    assert_eq!(lvl(&merge.inventory, "k_2"), Some(1));
+    assert!(local.diff(&merge.inventory).is_empty());
+}
+
+#[test]
+fn merge_without_base() {
+    let local = get_dated_inventory(&[("d_1", 2, 5), ("k_1", 1, 1)]);
+    let incoming = get_dated_inventory(&[("d_1", 1, 3), ("d_2", 1, 3)]);
+
+    let merge = local.merge_three_way(None, &incoming);
+    assert_eq!(lvl(&merge.inventory, "d_1"), Some(2));
+    assert_eq!(lvl(&merge.inventory, "d_2"), Some(1));
+    assert_eq!(lvl(&merge.inventory, "k_1"), Some(1));
+    assert_eq!(merge.conflicts.len(), 1);
+    assert_eq!(merge.conflicts[0].kept, MergeSide::Local);
+}
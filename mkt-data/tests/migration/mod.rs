@@ -0,0 +1,66 @@
+use mkt_data::migration::*;
+use mkt_data::*;
+
+#[test]
+fn unversioned_documents() {
+    let data =
+        MktData::from_json(r#"{"courses":{},"drivers":{},"karts":{},"gliders":{}}"#).unwrap();
+    assert_eq!(data.version, MKT_DATA_VERSION);
+
+    let inv = MktInventory::from_json(
+        r#"{"drivers":{"d_mario":{"id":"d_mario","lvl":3,"points":500}},"karts":{},"gliders":{}}"#,
+    )
+    .unwrap();
+    assert_eq!(inv.version, MKT_INVENTORY_VERSION);
+    assert_eq!(inv.drivers["d_mario"].lvl, 3);
+
+    let hashes = MktItemHashes::from_json(r#"{"hashes":{"d_mario":["abc"]}}"#).unwrap();
+    assert_eq!(hashes.version, MKT_HASHES_VERSION);
+    assert_eq!(hashes.hashes["d_mario"], vec!["abc".to_string()]);
+}
+
+#[test]
+fn current_version_round_trip() {
+    let mut inv = MktInventory::new();
+    inv.drivers
+        .insert("d_mario".into(), OwnedItem::new("d_mario".into(), 2, 400));
+    let json = inv.to_json().unwrap();
+    assert!(json.contains(&format!("\"version\": {}", MKT_INVENTORY_VERSION)));
+
+    let inv = MktInventory::from_json(&json).unwrap();
+    assert_eq!(inv.drivers["d_mario"].points, 400);
+}
+
+#[test]
+fn newer_version_is_rejected() {
+    let json = format!(
+        r#"{{"version":{},"drivers":{{}},"karts":{{}},"gliders":{{}}}}"#,
+        MKT_INVENTORY_VERSION + 1
+    );
+    assert!(MktInventory::from_json(&json).is_err());
+}
+
+#[test]
+fn point_rules_from_hardcoded_ladders() {
+    let json = r#"{"version":1,"courses":{},"drivers":{
+        "d_mario":{"sort":1,"id":"d_mario","name":"Mario","i_type":"Driver","rarity":"Normal","favorite_courses":[],"hashes":[]},
+        "d_mii_racing_suit_mario":{"sort":2,"id":"d_mii_racing_suit_mario","name":"Mii Racing Suit Mario","i_type":"Driver","rarity":"HighEnd","favorite_courses":[],"hashes":[]}
+    },"karts":{},"gliders":{}}"#;
+    let data = MktData::from_json(json).unwrap();
+    assert_eq!(data.version, MKT_DATA_VERSION);
+    assert_eq!(
+        data.rules.ladder_name(&data.drivers["d_mario"]),
+        "driver_normal"
+    );
+    let mii = data.point_ladder(&data.drivers["d_mii_racing_suit_mario"]);
+    assert_eq!(mii.unwrap().max_points(), 1440);
+}
+
+#[test]
+fn score_weights_default() {
+    // rules saved before the score weights
+    let json = r#"{"version":2,"courses":{},"drivers":{},"karts":{},"gliders":{},
+        "rules":{"ladders":{}}}"#;
+    let data = MktData::from_json(json).unwrap();
+    assert_eq!(data.rules.score_weights(), score::ScoreWeights::default());
+}
@@ -0,0 +1,106 @@
+mod alias;
+mod bundle;
+mod costs;
+mod coverage;
+mod diff;
+mod error;
+mod history;
+mod merge;
+mod migration;
+mod names;
+mod planner;
+mod rules;
+mod score;
+mod skill;
+mod tour;
+mod validation;
+
+use mkt_data::*;
+
+// small hand made data set, easier to reason about than the real one
+//
+// courses:  c_a   c_b   c_c   c_d
+// drivers:  d_1   d_1   d_2   -
+//           d_2*        d_3
+// karts:    k_1   k_1   k_1   k_2
+// gliders:  g_1   g_2   g_2   g_2
+//
+// * level 3 requirement, everything else is level 1
+pub fn get_test_data() -> MktData {
+    let mut data = MktData::new();
+    for (i, name) in ["A", "B", "C", "D"].iter().enumerate() {
+        let course = Course::new(name.to_string(), Some(i as u32 + 1));
+        data.courses.insert(course.id.clone(), course);
+    }
+    for (i, (i_type, name)) in [
+        (ItemType::Driver, "1"),
+        (ItemType::Driver, "2"),
+        (ItemType::Driver, "3"),
+        (ItemType::Kart, "1"),
+        (ItemType::Kart, "2"),
+        (ItemType::Glider, "1"),
+        (ItemType::Glider, "2"),
+    ]
+    .iter()
+    .enumerate()
+    {
+        let item = Item::new(
+            *i_type,
+            Rarity::Normal,
+            name.to_string(),
+            Some(i as u32 + 1),
+        );
+        match i_type {
+            ItemType::Driver => data.drivers.insert(item.id.clone(), item),
+            ItemType::Kart => data.karts.insert(item.id.clone(), item),
+            ItemType::Glider => data.gliders.insert(item.id.clone(), item),
+        };
+    }
+    for (course, item, lvl) in [
+        ("c_a", "d_1", 1),
+        ("c_a", "d_2", 3),
+        ("c_b", "d_1", 1),
+        ("c_c", "d_2", 1),
+        ("c_c", "d_3", 1),
+        ("c_a", "k_1", 1),
+        ("c_b", "k_1", 1),
+        ("c_c", "k_1", 1),
+        ("c_d", "k_2", 1),
+        ("c_a", "g_1", 1),
+        ("c_b", "g_2", 1),
+        ("c_c", "g_2", 1),
+        ("c_d", "g_2", 1),
+    ] {
+        add_favorite(&mut data, course, item, lvl);
+    }
+    data
+}
+
+pub fn add_favorite(data: &mut MktData, course: &str, item: &str, lvl: ItemLvl) {
+    data.courses
+        .get_mut(course)
+        .unwrap()
+        .favorite_items
+        .insert((item.to_string(), lvl).into());
+    let item = match item_type_from_id(item).unwrap() {
+        ItemType::Driver => data.drivers.get_mut(item),
+        ItemType::Kart => data.karts.get_mut(item),
+        ItemType::Glider => data.gliders.get_mut(item),
+    };
+    item.unwrap()
+        .favorite_courses
+        .insert((course.to_string(), lvl).into());
+}
+
+pub fn get_test_inventory(items: &[(&str, ItemLvl)]) -> MktInventory {
+    let mut inv = MktInventory::new();
+    for (id, lvl) in items {
+        let item = OwnedItem::new(id.to_string(), *lvl, 0);
+        inv.update_inventory(MktInventory::from_item(
+            item_type_from_id(id).unwrap(),
+            item,
+        ));
+    }
+    inv.clear_dates();
+    inv
+}
@@ -0,0 +1,95 @@
+use std::convert::TryFrom;
+
+use mkt_data::*;
+
+use crate::get_test_data;
+
+#[test]
+fn find_by_localized_name() {
+    let mut data = get_test_data();
+    data.drivers
+        .get_mut("d_1")
+        .unwrap()
+        .names
+        .insert(Locale::Fr, "Un Élève".into());
+    data.courses
+        .get_mut("c_b")
+        .unwrap()
+        .names
+        .insert(Locale::Jp, "Bee".into());
+
+    assert_eq!(
+        data.find_item_by_name("un  eleve").map(|i| i.id.as_str()),
+        Some("d_1")
+    );
+    assert_eq!(
+        data.find_item_by_name("1").map(|i| i.id.as_str()),
+        Some("d_1")
+    );
+    assert_eq!(
+        data.find_course_by_name("BEE").map(|c| c.id.as_str()),
+        Some("c_b")
+    );
+    assert!(data.find_item_by_name("Deux").is_none());
+    assert_eq!(
+        data.item_name_index().get("Un Eleve", None),
+        Some(&"d_1".to_string())
+    );
+}
+
+#[test]
+fn name_index_collisions() {
+    let mut data = get_test_data();
+    data.drivers
+        .get_mut("d_3")
+        .unwrap()
+        .names
+        .insert(Locale::Es, "Tres".into());
+
+    // the test items are named after their number, whatever their type
+    let index = data.item_name_index();
+    let collisions = index.collisions();
+    assert_eq!(collisions.len(), 2);
+    assert_eq!(
+        collisions[0],
+        (
+            &"1".to_string(),
+            &vec!["d_1".to_string(), "g_1".to_string(), "k_1".to_string()]
+        )
+    );
+    assert_eq!(index.get("1", None), None);
+    assert_eq!(
+        index.get("1", Some(ItemType::Kart)),
+        Some(&"k_1".to_string())
+    );
+    assert_eq!(index.get("tres", None), Some(&"d_3".to_string()));
+    assert_eq!(index.get("3", None), Some(&"d_3".to_string()));
+}
+
+#[test]
+fn name_fallback() {
+    let mut data = get_test_data();
+    let driver = data.drivers.get_mut("d_2").unwrap();
+    driver.names.insert(Locale::De, "Zwei".into());
+
+    assert_eq!(driver.get_name(Locale::De), "Zwei");
+    assert_eq!(driver.get_name(Locale::Es), "2");
+    assert_eq!(Locale::try_from("FR"), Ok(Locale::Fr));
+    assert!(Locale::try_from("xx").is_err());
+}
+
+#[test]
+fn names_serialization() {
+    let mut data = get_test_data();
+    let json = data.to_json().unwrap();
+    assert!(!json.contains("\"names\""));
+
+    data.courses
+        .get_mut("c_a")
+        .unwrap()
+        .names
+        .insert(Locale::It, "Ah".into());
+    let json = data.to_json().unwrap();
+    assert!(json.contains("\"names\""));
+    assert_eq!(MktData::from_json(&json).unwrap().courses, data.courses);
+}
@@ -0,0 +1,112 @@
+use itertools::Itertools;
+use mkt_data::planner::*;
+use mkt_data::*;
+
+use crate::{add_favorite, get_test_data, get_test_inventory};
+
+#[test]
+fn plan_from_scratch() {
+    let data = get_test_data();
+    let plan = plan_coverage(&data, &MktInventory::new());
+
+    let items = plan
+        .items
+        .iter()
+        .map(|i| (i.id.as_str(), i.lvl, i.courses.len()))
+        .collect_vec();
+    assert_eq!(
+        items,
+        vec![
+            ("d_1", 1, 2),
+            ("d_2", 1, 1),
+            ("k_1", 1, 3),
+            ("k_2", 1, 1),
+            ("g_2", 1, 3),
+            ("g_1", 1, 1),
+        ]
+    );
+    assert_eq!(
+        plan.uncoverable,
+        vec![UncoverableCourse {
+            id: "c_d".into(),
+            i_type: ItemType::Driver
+        }]
+    );
+}
+
+#[test]
+fn plan_from_inventory() {
+    let data = get_test_data();
+    let inv = get_test_inventory(&[("d_2", 1), ("k_1", 1), ("g_2", 1)]);
+    let plan = plan_coverage(&data, &inv);
+
+    // d_1 covers both c_a and c_b, owned d_2 would need level 3 for c_a alone
+    let items = plan
+        .items
+        .iter()
+        .map(|i| (i.id.as_str(), i.lvl, i.owned_lvl))
+        .collect_vec();
+    assert_eq!(
+        items,
+        vec![("d_1", 1, None), ("k_2", 1, None), ("g_1", 1, None)]
+    );
+
+    let inv = get_test_inventory(&[("d_2", 1), ("k_1", 1), ("k_2", 1), ("g_1", 1), ("g_2", 1)]);
+    let plan = plan_coverage(&data, &inv);
+    let items = plan
+        .items
+        .iter()
+        .map(|i| (i.id.as_str(), i.lvl, i.owned_lvl))
+        .collect_vec();
+    assert_eq!(items, vec![("d_1", 1, None)]);
+}
+
+#[test]
+fn plan_prefers_owned_level_up() {
+    let mut data = get_test_data();
+    // d_1 no longer covers c_b
+    data.courses
+        .get_mut("c_b")
+        .unwrap()
+        .favorite_items
+        .retain(|r| r.id != "d_1");
+    data.drivers
+        .get_mut("d_1")
+        .unwrap()
+        .favorite_courses
+        .retain(|c| c.id != "c_b");
+
+    let inv = get_test_inventory(&[("d_2", 1)]);
+    let plan = plan_coverage(&data, &inv);
+    let items = plan
+        .items_of_type(ItemType::Driver)
+        .map(|i| (i.id.as_str(), i.lvl, i.owned_lvl))
+        .collect_vec();
+    assert_eq!(items, vec![("d_2", 3, Some(1))]);
+    assert_eq!(plan.level_ups().count(), 1);
+}
+
+#[test]
+fn level_ups_ranking() {
+    let mut data = get_test_data();
+    add_favorite(&mut data, "c_d", "d_3", 6);
+    add_favorite(&mut data, "c_b", "d_3", 8);
+
+    let inv = get_test_inventory(&[("d_2", 1), ("d_3", 2), ("k_1", 1)]);
+    let level_ups = rank_level_ups(&data, &inv)
+        .into_iter()
+        .map(|l| (l.id, l.from, l.to, l.courses.len()))
+        .collect_vec();
+    assert_eq!(
+        level_ups,
+        vec![
+            ("d_3".into(), 2, 8, 2),
+            ("d_2".into(), 1, 3, 1),
+            ("d_3".into(), 2, 6, 1),
+        ]
+    );
+
+    // c_a is already covered by d_1
+    let inv = get_test_inventory(&[("d_1", 1), ("d_2", 1)]);
+    assert!(rank_level_ups(&data, &inv).is_empty());
+}
@@ -0,0 +1,98 @@
+use mkt_data::rules::*;
+use mkt_data::validation::*;
+use mkt_data::*;
+
+use crate::get_test_data;
+
+#[test]
+fn default_ladders() {
+    let data = get_test_data();
+    let ladder = data.point_ladder(&data.drivers["d_1"]).unwrap();
+    assert_eq!(ladder.caps, vec![400, 600, 648, 704, 760]);
+    assert_eq!(ladder.valid_points().len(), 46);
+    assert_eq!(ladder.cap_tier(620), 1);
+    assert_eq!(ladder.cap_tier(800), 0);
+
+    let item = Item::new(ItemType::Glider, Rarity::Super, "X".into(), None);
+    let points = data.point_ladder(&item).unwrap().valid_points();
+    assert_eq!(&points[14..17], &[276, 280, 285]);
+    assert_eq!(points.last(), Some(&450));
+}
+
+#[test]
+fn item_override() {
+    let mut data = get_test_data();
+    data.rules
+        .ladders
+        .insert("tiny".into(), PointLadder::new(&[10, 20], &[(10, 5)]));
+    data.rules.items.insert("k_1".into(), "tiny".into());
+
+    let ladder = data.point_ladder(&data.karts["k_1"]).unwrap();
+    assert_eq!(ladder.valid_points(), vec![10, 15, 20]);
+
+    let mut owned = OwnedItem::new("k_1".into(), 1, 12);
+    owned.normalize_points(ladder);
+    assert_eq!(owned.points, 15);
+    owned.increment_points(ladder);
+    owned.increment_points(ladder);
+    assert_eq!(owned.points, 20);
+    owned.decrement_points(ladder);
+    assert_eq!(owned.points, 15);
+    owned.points = 300;
+    owned.normalize_points(ladder);
+    assert_eq!(owned.points, 20);
+}
+
+#[test]
+fn invalid_ladders() {
+    let mut data = get_test_data();
+    data.rules.items.insert("d_1".into(), "gone".into());
+    data.rules
+        .ladders
+        .insert("unsorted".into(), PointLadder::new(&[20, 10], &[(10, 5)]));
+    assert_eq!(
+        data.validate().issues,
+        vec![
+            ValidationIssue::MissingPointLadder {
+                item: "d_1".into(),
+                ladder: "gone".into()
+            },
+            ValidationIssue::InvalidPointLadder {
+                ladder: "unsorted".into()
+            },
+        ]
+    );
+}
+
+#[test]
+fn rules_round_trip() {
+    let mut data = get_test_data();
+    data.rules.items.insert("d_1".into(), MII_LADDER.into());
+    let data = MktData::from_json(&data.to_json().unwrap()).unwrap();
+    assert_eq!(data.rules.ladder_name(&data.drivers["d_1"]), MII_LADDER);
+    assert_eq!(data.rules, {
+        let mut rules = PointRules::default();
+        rules.items.insert("d_1".into(), MII_LADDER.into());
+        rules
+    });
+}
+
+#[test]
+fn merge_keeps_score_weights() {
+    let mut rules = PointRules::default();
+    let weights = score::ScoreWeights {
+        top: 1.0,
+        middle: 0.9,
+        bottom: 0.8,
+    };
+    rules.score_weights = Some(weights);
+    // rules without weights, like the updates
+    rules.merge(PointRules::default());
+    assert_eq!(rules.score_weights(), weights);
+
+    rules.merge(PointRules {
+        score_weights: Some(score::ScoreWeights::default()),
+        ..Default::default()
+    });
+    assert_eq!(rules.score_weights(), score::ScoreWeights::default());
+}
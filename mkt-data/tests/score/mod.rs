@@ -0,0 +1,99 @@
+use mkt_data::score::*;
+use mkt_data::*;
+
+use crate::{get_test_data, get_test_inventory};
+
+fn get_score_inventory() -> MktInventory {
+    let mut inv = get_test_inventory(&[("d_1", 1), ("d_2", 1), ("k_1", 1), ("g_1", 1), ("g_2", 1)]);
+    inv.drivers.get_mut("d_1").unwrap().points = 500;
+    inv.drivers.get_mut("d_2").unwrap().points = 800;
+    inv.karts.get_mut("k_1").unwrap().points = 300;
+    inv.gliders.get_mut("g_1").unwrap().points = 200;
+    inv.gliders.get_mut("g_2").unwrap().points = 320;
+    inv
+}
+
+#[test]
+fn shelves() {
+    let mut data = get_test_data();
+    data.drivers
+        .get_mut("d_3")
+        .unwrap()
+        .favored_courses
+        .insert(("c_a".into(), 0).into());
+    let owned = OwnedItem::new("d_2".into(), 1, 0);
+    assert_eq!(shelf(&data.drivers["d_2"], &owned, "c_c"), Shelf::Top);
+    // below the required level
+    assert_eq!(shelf(&data.drivers["d_2"], &owned, "c_a"), Shelf::Middle);
+    assert_eq!(shelf(&data.drivers["d_2"], &owned, "c_b"), Shelf::Bottom);
+    let owned = OwnedItem::new("d_3".into(), 1, 0);
+    assert_eq!(shelf(&data.drivers["d_3"], &owned, "c_a"), Shelf::Middle);
+}
+
+#[test]
+fn loadout_score() {
+    let data = get_test_data();
+    let inv = get_score_inventory();
+
+    let score = score_loadout(&data, &inv, "c_a", ["d_2", "k_1", "g_1"]).unwrap();
+    // points are capped at 760
+    assert_eq!(score.driver.points, 760);
+    assert_eq!(score.driver.cap_tier, 3);
+    assert_eq!(score.driver.shelf, Shelf::Middle);
+    assert_eq!(score.driver.score, 570);
+    assert_eq!(score.kart.score, 300);
+    assert_eq!(score.glider.score, 200);
+    assert_eq!(score.total, 1070);
+
+    assert!(score_loadout(&data, &inv, "c_a", ["d_3", "k_1", "g_1"]).is_none());
+}
+
+#[test]
+fn ranked_items() {
+    let mut data = get_test_data();
+    let inv = get_score_inventory();
+
+    let ranked = rank_items(&data, &inv, "c_c", ItemType::Driver);
+    assert_eq!(
+        ranked
+            .iter()
+            .map(|i| (i.id.as_str(), i.score))
+            .collect::<Vec<_>>(),
+        vec![("d_2", 760), ("d_1", 250)]
+    );
+
+    // the weights come from the data rules
+    data.rules.score_weights = Some(ScoreWeights {
+        top: 1.0,
+        middle: 1.0,
+        bottom: 0.1,
+    });
+    let data = MktData::from_json(&data.to_json().unwrap()).unwrap();
+    let ranked = rank_items(&data, &inv, "c_a", ItemType::Glider);
+    assert_eq!(
+        ranked
+            .iter()
+            .map(|i| (i.id.as_str(), i.score))
+            .collect::<Vec<_>>(),
+        vec![("g_1", 200), ("g_2", 32)]
+    );
+}
+
+#[test]
+fn best_loadout_per_course() {
+    let data = get_test_data();
+    let inv = get_score_inventory();
+    let ids = |items: &[ItemScore]| items.iter().map(|s| s.id.clone()).collect::<Vec<_>>();
+
+    // d_2 has more points but is below the required level
+    let loadout = best_loadout(&data, &inv, "c_a", 1);
+    assert_eq!(ids(&loadout.drivers), vec!["d_1", "d_2"]);
+    assert_eq!(ids(&loadout.karts), vec!["k_1"]);
+    assert_eq!(ids(&loadout.gliders), vec!["g_1", "g_2"]);
+
+    // no requirement met, the points decide
+    let loadout = best_loadout(&data, &inv, "c_d", 0);
+    assert_eq!(ids(loadout.items(ItemType::Driver)), vec!["d_2"]);
+    assert_eq!(loadout.drivers[0].shelf, Shelf::Bottom);
+    assert_eq!(ids(loadout.items(ItemType::Glider)), vec!["g_2"]);
+}
@@ -0,0 +1,67 @@
+use mkt_data::*;
+
+use crate::get_test_data;
+
+#[test]
+fn skill_levels() {
+    let mut data = get_test_data();
+    let kart = data.karts.get_mut("k_1").unwrap();
+    assert_eq!(kart.valid_skill_levels(), vec![1]);
+
+    let mut skill = Skill::new("Rocket Start Plus".into());
+    skill.values = vec![5, 10, 15];
+    kart.skill = Some(skill);
+    assert_eq!(kart.valid_skill_levels(), vec![1, 2, 3]);
+
+    let skill = kart.skill.as_ref().unwrap();
+    assert_eq!(skill.value(0), None);
+    assert_eq!(skill.value(2), Some(10));
+    assert_eq!(skill.value(4), None);
+
+    // the driver levels come from the skill values as well
+    let driver = data.drivers.get_mut("d_1").unwrap();
+    assert_eq!(driver.valid_skill_levels(), vec![1]);
+    let mut skill = Skill::new("Boomerang Flower".into());
+    skill.values = vec![1, 2, 3, 4, 5, 6];
+    driver.skill = Some(skill);
+    assert_eq!(driver.valid_skill_levels().len(), 6);
+}
+
+#[test]
+fn merge_skill_level() {
+    let mut item = OwnedItem::new("d_1".into(), 1, 400);
+    item.skill_lvl = Some(3);
+
+    // unknown skill level doesn't replace the known one
+    item.merge(OwnedItem::new("d_1".into(), 2, 400));
+    assert_eq!(item.lvl, 2);
+    assert_eq!(item.skill_lvl, Some(3));
+
+    let mut new_item = OwnedItem::new("d_1".into(), 2, 400);
+    new_item.skill_lvl = Some(2);
+    item.improve(new_item.clone());
+    assert_eq!(item.skill_lvl, Some(3));
+    item.merge(new_item);
+    assert_eq!(item.skill_lvl, Some(2));
+}
+
+#[test]
+fn skill_serialization() {
+    let mut data = get_test_data();
+    assert!(!data.to_json().unwrap().contains("\"skill\""));
+
+    data.drivers.get_mut("d_1").unwrap().skill = Some(Skill::new("Boomerang Flower".into()));
+    let json = data.to_json().unwrap();
+    assert!(json.contains("\"skill\""));
+    assert!(!json.contains("\"values\""));
+    assert_eq!(
+        MktData::from_json(&json).unwrap().drivers["d_1"].skill,
+        data.drivers["d_1"].skill
+    );
+
+    let inv = MktInventory::from_json(
+        r#"{"drivers": {"d_1": {"id": "d_1", "lvl": 1, "points": 0}}, "karts": {}, "gliders": {}}"#,
+    )
+    .unwrap();
+    assert_eq!(inv.drivers["d_1"].skill_lvl, None);
+}
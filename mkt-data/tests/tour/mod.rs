@@ -0,0 +1,84 @@
+use chrono::{NaiveDate, TimeZone, Utc};
+use mkt_data::planner::*;
+use mkt_data::validation::*;
+use mkt_data::*;
+
+use crate::{get_test_data, get_test_inventory};
+
+fn date(m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2022, m, d).unwrap()
+}
+
+fn get_tour_data() -> MktData {
+    let mut data = get_test_data();
+    let mut tour = Tour::new("Winter Tour".into(), date(1, 4), date(1, 17), Some(1));
+    tour.courses = vec!["c_c".into(), "c_d".into()];
+    tour.spotlight_items = vec!["d_3".into()];
+    data.tours.insert(tour.id.clone(), tour);
+    let mut tour = Tour::new("Spring Tour".into(), date(1, 18), date(1, 31), Some(2));
+    tour.courses = vec!["c_a".into()];
+    data.tours.insert(tour.id.clone(), tour);
+    data
+}
+
+#[test]
+fn current_tour() {
+    let data = get_tour_data();
+    let at = |m, d, h| Utc.with_ymd_and_hms(2022, m, d, h, 0, 0).unwrap();
+    assert_eq!(data.current_tour(at(1, 4, 0)).unwrap().id, "t_winter_tour");
+    assert_eq!(
+        data.current_tour(at(1, 17, 23)).unwrap().id,
+        "t_winter_tour"
+    );
+    assert_eq!(data.current_tour(at(1, 18, 0)).unwrap().id, "t_spring_tour");
+    assert!(data.current_tour(at(2, 1, 0)).is_none());
+}
+
+#[test]
+fn tour_coverage() {
+    let data = get_tour_data();
+    let tour_data = data.for_tour(&data.tours["t_winter_tour"]);
+    assert_eq!(tour_data.courses.len(), 2);
+    assert!(tour_data.drivers["d_1"].favorite_courses.is_empty());
+
+    let inv = get_test_inventory(&[("d_1", 1), ("k_2", 1)]);
+    assert_eq!(covered_courses(&tour_data, &inv, ItemType::Driver).len(), 0);
+    assert_eq!(covered_courses(&tour_data, &inv, ItemType::Kart).len(), 1);
+
+    let plan = plan_coverage(&tour_data, &inv);
+    assert_eq!(
+        plan.items_of_type(ItemType::Kart)
+            .map(|i| i.id.as_str())
+            .collect::<Vec<_>>(),
+        vec!["k_1"]
+    );
+}
+
+#[test]
+fn invalid_tours() {
+    let mut data = get_tour_data();
+    let tour = data.tours.get_mut("t_spring_tour").unwrap();
+    tour.courses.push("c_x".into());
+    tour.end = date(1, 1);
+    assert_eq!(
+        data.validate().issues,
+        vec![
+            ValidationIssue::InvalidTourDates {
+                tour: "t_spring_tour".into(),
+            },
+            ValidationIssue::MissingTourEntry {
+                tour: "t_spring_tour".into(),
+                id: "c_x".into(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn tours_serialization() {
+    let data = get_tour_data();
+    let json = data.to_json().unwrap();
+    assert!(json.contains("\"start\": \"2022-01-04\""));
+    assert_eq!(MktData::from_json(&json).unwrap().tours, data.tours);
+    assert!(!get_test_data().to_json().unwrap().contains("\"tours\""));
+}
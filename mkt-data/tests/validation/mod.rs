@@ -0,0 +1,87 @@
+use mkt_data::validation::*;
+use mkt_data::*;
+
+use crate::get_test_data;
+
+#[test]
+fn valid_data() {
+    let report = get_test_data().validate();
+    assert!(report.is_ok(), "{}", report);
+}
+
+#[test]
+fn invalid_data() {
+    let mut data = get_test_data();
+    data.courses
+        .get_mut("c_a")
+        .unwrap()
+        .favorite_items
+        .insert(("d_4".into(), 1).into());
+    data.karts
+        .get_mut("k_2")
+        .unwrap()
+        .favorite_courses
+        .insert(("c_b".into(), 1).into());
+    data.gliders.get_mut("g_2").unwrap().sort = Some(6);
+    data.drivers.get_mut("d_3").unwrap().name = "Three".into();
+    let course = Course::new("!!".into(), Some(5));
+    data.courses.insert(course.id.clone(), course);
+
+    assert_eq!(
+        data.validate().issues,
+        vec![
+            ValidationIssue::UnparsableCourseId { id: "c_".into() },
+            ValidationIssue::MissingItem {
+                course: "c_a".into(),
+                item: "d_4".into()
+            },
+            ValidationIssue::IdMismatch {
+                id: "d_3".into(),
+                expected: "d_three".into()
+            },
+            ValidationIssue::FavoriteMismatch {
+                course: "c_b".into(),
+                item: "k_2".into(),
+                course_lvl: None,
+                item_lvl: Some(1)
+            },
+            ValidationIssue::DuplicateSort {
+                sort: 6,
+                ids: vec!["g_1".into(), "g_2".into()]
+            },
+        ]
+    );
+}
+
+#[test]
+fn favored_mismatch() {
+    let mut data = get_test_data();
+    data.courses
+        .get_mut("c_a")
+        .unwrap()
+        .favored_items
+        .insert(("d_1".into(), 0).into());
+    data.gliders
+        .get_mut("g_1")
+        .unwrap()
+        .favored_courses
+        .insert(("c_b".into(), 0).into());
+
+    assert_eq!(
+        data.validate().issues,
+        vec![
+            ValidationIssue::FavoredMismatch {
+                course: "c_a".into(),
+                item: "d_1".into(),
+                course_lvl: Some(0),
+                item_lvl: None
+            },
+            ValidationIssue::FavoredMismatch {
+                course: "c_b".into(),
+                item: "g_1".into(),
+                course_lvl: None,
+                item_lvl: Some(0)
+            },
+        ]
+    );
+}
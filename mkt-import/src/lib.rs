@@ -1,2 +1,3 @@
 #![feature(try_blocks)]
 pub mod screenshot;
+pub mod sheet;
@@ -901,6 +901,7 @@ pub fn screenshots_to_inventory(
     if let Some(hashes) = hashes.cloned() {
         data_hashes.merge(hashes);
     }
+    data_hashes.resolve_items(data);
     let mut items = screenshots_to_owned_items(screenshots, Some(data_hashes));
     deduce_missing_owned_items(&mut items, data);
 
@@ -914,12 +915,14 @@ pub fn screenshots_to_inventory(
         .filter(|i| i.id.is_some() && i.lvl.is_some() && i.points.is_some())
         .flat_map(result_owned_item)
         .update(|i| {
-            if let Some(item) = data.get_item(&i.id) {
-                i.normalize_points(item);
+            if let Some(ladder) = data.get_item(&i.id).and_then(|i| data.point_ladder(i)) {
+                i.normalize_points(ladder);
             }
         })
         .collect();
-    inv.update_inventory(MktInventory::from_items(items, data));
+    // the ids come from the hashes, already resolved
+    let (items, _) = MktInventory::from_items(items, data);
+    inv.update_inventory(items);
 
     (inv, hashes)
 }
@@ -0,0 +1,81 @@
+use std::convert::TryFrom;
+
+use chrono::Utc;
+use mkt_data::{
+    rules::PointLadder, ItemLvl, ItemPoints, ItemType, MktData, MktInventory, OwnedItem,
+};
+
+// One item per line, like the BGR sheet: name, type (D, K or G), level and points cap
+// tier, separated by tabs. Names are matched in any locale, not owned items have level 0.
+// Owned points are kept when they are in the same tier, the changed items are dated now.
+// Returns the unknown names and the lines with an invalid level too, they are skipped.
+pub fn sheet_to_inventory(
+    text: &str,
+    data: &MktData,
+    inv: &MktInventory,
+) -> (MktInventory, Vec<String>, Vec<(String, ItemLvl)>) {
+    let item_names = data.item_name_index();
+    let now = Utc::now();
+    let mut items = vec![];
+    let mut unknown = vec![];
+    let mut invalid = vec![];
+
+    for line in text.lines() {
+        let mut cells = line.split('\t').map(str::trim);
+        let (name, i_type, lvl, tier) = match (cells.next(), cells.next(), cells.next()) {
+            (Some(name), Some(i_type), Some(lvl)) if !name.is_empty() => (
+                name,
+                ItemType::try_from(i_type).ok(),
+                lvl.parse().unwrap_or(0),
+                cells.next().and_then(|t| t.parse().ok()).unwrap_or(0),
+            ),
+            _ => continue,
+        };
+        let item = match item_names
+            .get(name, i_type)
+            .and_then(|id| data.get_item(id))
+        {
+            Some(item) => item,
+            None => {
+                unknown.push(name.to_string());
+                continue;
+            }
+        };
+        if lvl == 0 {
+            continue;
+        }
+        if !item.valid_levels().contains(&lvl) {
+            invalid.push((name.to_string(), lvl));
+            continue;
+        }
+
+        let old = inv.get_item(&item.id);
+        let mut owned = old
+            .cloned()
+            .unwrap_or_else(|| OwnedItem::new(item.id.clone(), lvl, 0));
+        owned.lvl = lvl;
+        if let Some(ladder) = data.point_ladder(item) {
+            if owned.points == 0 || owned.point_cap_tier(ladder) != tier {
+                owned.points = tier_points(ladder, tier);
+            }
+        }
+        // newer than the inventory, for the merges
+        if old.map(|o| (o.lvl, o.points)) != Some((owned.lvl, owned.points)) {
+            owned.last_changed = Some(now);
+        }
+        items.push(owned);
+    }
+
+    // the ids come from the data
+    let (inv, _) = MktInventory::from_items(items, data);
+    (inv, unknown, invalid)
+}
+
+// the lowest points of the tier
+fn tier_points(ladder: &PointLadder, tier: u8) -> ItemPoints {
+    ladder
+        .valid_points()
+        .into_iter()
+        .find(|p| ladder.cap_tier(*p) == tier)
+        .unwrap_or_default()
+}
@@ -2,6 +2,7 @@ mod drivers;
 mod gliders;
 mod hash;
 mod karts;
+mod sheet;
 
 use std::fs;
 use std::path::PathBuf;
@@ -0,0 +1,65 @@
+use mkt_data::*;
+use mkt_import::sheet::sheet_to_inventory;
+
+fn get_data() -> MktData {
+    let mut data = MktData::new();
+    let mut mario = Item::new(ItemType::Driver, Rarity::Normal, "Mario".into(), Some(1));
+    mario.names.insert(Locale::Fr, "Mário".into());
+    let kart = Item::new(ItemType::Kart, Rarity::Normal, "Mario".into(), Some(2));
+    let glider = Item::new(
+        ItemType::Glider,
+        Rarity::Normal,
+        "Super Glider".into(),
+        Some(3),
+    );
+    data.drivers.insert(mario.id.clone(), mario);
+    data.karts.insert(kart.id.clone(), kart);
+    data.gliders.insert(glider.id.clone(), glider);
+    data
+}
+
+#[test]
+fn sheet_import() {
+    let data = get_data();
+    let mut inv = MktInventory::new();
+    inv.drivers
+        .insert("d_mario".into(), OwnedItem::new("d_mario".into(), 2, 616));
+    inv.clear_dates();
+
+    let sheet = "MARIO\tD\t3\t1\nmario\tK\t1\t0\nSUPER GLIDER\tG\t0\t0\nLUIGI\tD\t1\t0\n";
+    let (new_inv, unknown, invalid) = sheet_to_inventory(sheet, &data, &inv);
+
+    // same tier, the points are kept
+    let mario = &new_inv.drivers["d_mario"];
+    assert_eq!((mario.lvl, mario.points), (3, 616));
+    let kart = &new_inv.karts["k_mario"];
+    assert_eq!((kart.lvl, kart.points), (1, 200));
+    assert!(new_inv.gliders.is_empty());
+    assert_eq!(unknown, vec!["LUIGI".to_string()]);
+    assert!(invalid.is_empty());
+    // the changed items are newer than the inventory
+    assert!(mario.last_changed.is_some());
+
+    // localized name, in a higher tier
+    let (new_inv, _, _) = sheet_to_inventory("Mário\tD\t4\t2", &data, &inv);
+    let mario = &new_inv.drivers["d_mario"];
+    assert_eq!((mario.lvl, mario.points), (4, 656));
+
+    // unchanged items keep their dates
+    let (new_inv, _, _) = sheet_to_inventory("MARIO\tD\t2\t1", &data, &inv);
+    assert_eq!(new_inv.drivers["d_mario"].last_changed, None);
+}
+
+#[test]
+fn sheet_invalid_levels() {
+    let data = get_data();
+    let (new_inv, unknown, invalid) = sheet_to_inventory(
+        "MARIO\tD\t9\t0\nmario\tK\t8\t0\n",
+        &data,
+        &MktInventory::new(),
+    );
+    assert!(new_inv.drivers.is_empty());
+    assert_eq!(new_inv.karts["k_mario"].lvl, 8);
+    assert!(unknown.is_empty());
+    assert_eq!(invalid, vec![("MARIO".to_string(), 9)]);
+}
@@ -5,6 +5,8 @@ use yew_agent::{
     AgentLink,
 };
 
+use crate::storage;
+
 pub enum Msg {
     Data(Box<MktData>),
 }
@@ -36,12 +38,12 @@ impl Store for DataStore {
                 link.send_input(DataRequest::Save);
             }
             DataRequest::Load => {
-                if let Ok(data) = LocalStorage::get("mkt_data") {
-                    link.send_message(Msg::Data(data));
+                if let Some(data) = storage::get_data() {
+                    link.send_message(Msg::Data(Box::new(data)));
                 }
             }
             DataRequest::Save => {
-                LocalStorage::set("mkt_data", &self.data).unwrap();
+                storage::set_data(&self.data);
             }
             DataRequest::Delete => {
                 LocalStorage::delete("mkt_data_date");
@@ -8,7 +8,13 @@ use super::{
     data::DataStore,
     inventory::{Inventory, InventoryRequest},
 };
-use mkt_data::{item_type_from_id, Course, CourseId, Item, ItemId, ItemType, OwnedItem};
+use chrono::Utc;
+use itertools::Itertools;
+use mkt_data::{
+    coverage::{CourseStats, CoverageReport, ItemStats},
+    rules::PointLadder,
+    Course, CourseId, Item, ItemId, ItemType, MktData, MktInventory, OwnedItem, Tour,
+};
 use yew_agent::{
     utils::store::{Bridgeable, ReadOnly, StoreWrapper},
     Agent, AgentLink, Bridge, Context, HandlerId,
@@ -23,33 +29,19 @@ pub enum Msg {
 pub enum DataInventoryRequest {
     Refresh,
     RefreshAll,
+    ToggleTour,
 }
 
 pub type Shared<T> = Rc<RwLock<T>>;
 
-pub struct CourseStats {
-    pub driver_count: usize,
-    pub kart_count: usize,
-    pub glider_count: usize,
-    pub driver_owned_count: usize,
-    pub kart_owned_count: usize,
-    pub glider_owned_count: usize,
-}
-
 pub struct DataInvCourse {
     pub data: Course,
     pub stats: Option<CourseStats>,
 }
 
-pub struct ItemStats {
-    pub fav_course_count: usize,
-    pub max_fav_course_count: usize,
-    pub add_course_count: usize,
-    pub max_add_course_count: usize,
-}
-
 pub struct DataInvItem {
     pub data: Item,
+    pub ladder: PointLadder,
     pub inv: Option<OwnedItem>,
     pub stats: Option<ItemStats>,
 }
@@ -60,75 +52,34 @@ pub struct DataInventory {
     pub drivers: HashMap<ItemId, Shared<DataInvItem>>,
     pub karts: HashMap<ItemId, Shared<DataInvItem>>,
     pub gliders: HashMap<ItemId, Shared<DataInvItem>>,
+    pub current_tour: Option<Tour>,
+    // the stats only count the courses of the current tour
+    pub tour_only: bool,
+    tour_data: Option<MktData>,
+    pub report: CoverageReport,
+    pub data: MktData,
+    pub inv: MktInventory,
+    // the items were rebuilt from new data, they need the whole inventory again
+    data_changed: bool,
 }
 
 impl DataInventory {
+    // the data the stats are computed from, only the current tour when filtered
+    pub fn scoped_data(&self) -> &MktData {
+        self.tour_data.as_ref().unwrap_or(&self.data)
+    }
+
+    fn update_tour_data(&mut self) {
+        let tour = self.current_tour.as_ref().filter(|_| self.tour_only);
+        self.tour_data = tour.map(|t| self.data.for_tour(t));
+    }
+
     fn update_stats(&mut self) {
-        // courses statistics
+        self.report = CoverageReport::new(self.scoped_data(), &self.inv);
         for course in self.courses.values() {
             let mut course = course.write().unwrap();
-
-            let mut driver_count = 0;
-            let mut kart_count = 0;
-            let mut glider_count = 0;
-            let mut driver_owned_count = 0;
-            let mut kart_owned_count = 0;
-            let mut glider_owned_count = 0;
-
-            for r in &course.data.favorite_items {
-                if let Some(i_type) = item_type_from_id(&r.id) {
-                    match i_type {
-                        ItemType::Driver => {
-                            driver_count += 1;
-                            if self
-                                .drivers
-                                .get(&r.id)
-                                .and_then(|i| i.read().unwrap().inv.as_ref().map(|i| i.lvl))
-                                .unwrap_or(0)
-                                >= r.lvl
-                            {
-                                driver_owned_count += 1;
-                            }
-                        }
-                        ItemType::Kart => {
-                            kart_count += 1;
-                            if self
-                                .karts
-                                .get(&r.id)
-                                .and_then(|i| i.read().unwrap().inv.as_ref().map(|i| i.lvl))
-                                .unwrap_or(0)
-                                >= r.lvl
-                            {
-                                kart_owned_count += 1;
-                            }
-                        }
-                        ItemType::Glider => {
-                            glider_count += 1;
-                            if self
-                                .gliders
-                                .get(&r.id)
-                                .and_then(|i| i.read().unwrap().inv.as_ref().map(|i| i.lvl))
-                                .unwrap_or(0)
-                                >= r.lvl
-                            {
-                                glider_owned_count += 1;
-                            }
-                        }
-                    }
-                }
-            }
-
-            course.stats = Some(CourseStats {
-                driver_count,
-                kart_count,
-                glider_count,
-                driver_owned_count,
-                kart_owned_count,
-                glider_owned_count,
-            })
+            course.stats = self.report.courses.get(&course.data.id).cloned();
         }
-
-        // items statistics
         for item in self
             .drivers
             .values()
@@ -136,54 +87,55 @@ impl DataInventory {
             .chain(self.gliders.values())
         {
             let mut item = item.write().unwrap();
+            item.stats = self.report.items.get(&item.data.id).cloned();
+        }
+    }
 
-            let mut fav_course_count = 0;
-            let mut max_fav_course_count = 0;
-            let mut add_course_count = 0;
-            let mut max_add_course_count = 0;
-
-            let lvl = item.inv.as_ref().map(|i| i.lvl).unwrap_or(0);
-            for r in &item.data.favorite_courses {
-                let add = self
-                    .courses
-                    .get(&r.id)
-                    .and_then(|c| {
-                        c.read()
-                            .unwrap()
-                            .stats
-                            .as_ref()
-                            .map(|s| match item.data.i_type {
-                                ItemType::Driver => s.driver_owned_count,
-                                ItemType::Kart => s.kart_owned_count,
-                                ItemType::Glider => s.glider_owned_count,
-                            })
-                    })
-                    .filter(|c| *c > 0)
-                    .map(|_| 0)
-                    .unwrap_or(1);
-                if lvl == 0 {
-                    if r.lvl == 1 {
-                        add_course_count += add;
-                    }
-                    max_add_course_count += add;
-                } else if lvl >= r.lvl {
-                    fav_course_count += 1;
-                } else {
-                    max_add_course_count += add;
-                }
-                max_fav_course_count += 1;
-            }
+    // only the courses and items touched by an item edit
+    fn update_item_stats(&mut self, id: &str) {
+        let inv = self.inv.get_item(id).cloned();
+        if let Some(item) = self.item(id) {
+            item.write().unwrap().inv = inv;
+        }
 
-            item.stats = Some(ItemStats {
-                fav_course_count,
-                max_fav_course_count,
-                add_course_count,
-                max_add_course_count,
-            })
+        let data = self.tour_data.as_ref().unwrap_or(&self.data);
+        let update = self.report.update_item(data, &self.inv, id);
+        for id in &update.courses {
+            if let Some(course) = self.courses.get(id) {
+                course.write().unwrap().stats = self.report.courses.get(id).cloned();
+            }
         }
+        for id in &update.items {
+            if let Some(item) = self.item(id) {
+                item.write().unwrap().stats = self.report.items.get(id).cloned();
+            }
+        }
+    }
+
+    fn item(&self, id: &str) -> Option<&Shared<DataInvItem>> {
+        self.drivers
+            .get(id)
+            .or_else(|| self.karts.get(id))
+            .or_else(|| self.gliders.get(id))
     }
 }
 
+// above this, a full update is faster than many small ones
+const INCREMENTAL_UPDATE_LIMIT: usize = 10;
+
+// items added, removed or edited, the dates are ignored
+fn changed_items(old: &MktInventory, new: &MktInventory) -> Vec<ItemId> {
+    let state =
+        |inv: &MktInventory, id: &str| inv.get_item(id).map(|i| (i.lvl, i.points, i.skill_lvl));
+    [ItemType::Driver, ItemType::Kart, ItemType::Glider]
+        .iter()
+        .flat_map(|i_type| old.items(*i_type).keys().chain(new.items(*i_type).keys()))
+        .unique()
+        .filter(|id| state(old, id) != state(new, id))
+        .cloned()
+        .collect()
+}
+
 pub struct DataInventoryAgent {
     /// Currently subscribed components and agents
     handlers: HashSet<HandlerId>,
@@ -229,8 +181,9 @@ impl Agent for DataInventoryAgent {
     fn update(&mut self, msg: Self::Message) {
         // update state
         match msg {
-            Msg::DataStore(data) => {
-                let data = &data.borrow().data;
+            Msg::DataStore(store) => {
+                let borrowed = store.borrow();
+                let data = &borrowed.data;
                 let mut state = self.state.write().unwrap();
 
                 // update courses
@@ -252,12 +205,15 @@ impl Agent for DataInventoryAgent {
                 // update drivers
                 let mut new_drivers = HashMap::<ItemId, Shared<DataInvItem>>::default();
                 for driver in data.drivers.values() {
+                    let ladder = data.point_ladder(driver).cloned().unwrap_or_default();
                     let new_driver = if let Some(i) = state.drivers.remove(&driver.id) {
                         i.write().unwrap().data = driver.clone();
+                        i.write().unwrap().ladder = ladder;
                         i
                     } else {
                         Rc::new(RwLock::new(DataInvItem {
                             data: driver.clone(),
+                            ladder,
                             inv: None,
                             stats: None,
                         }))
@@ -269,12 +225,15 @@ impl Agent for DataInventoryAgent {
                 // update karts
                 let mut new_karts = HashMap::<ItemId, Shared<DataInvItem>>::default();
                 for kart in data.karts.values() {
+                    let ladder = data.point_ladder(kart).cloned().unwrap_or_default();
                     let new_kart = if let Some(i) = state.karts.remove(&kart.id) {
                         i.write().unwrap().data = kart.clone();
+                        i.write().unwrap().ladder = ladder;
                         i
                     } else {
                         Rc::new(RwLock::new(DataInvItem {
                             data: kart.clone(),
+                            ladder,
                             inv: None,
                             stats: None,
                         }))
@@ -286,12 +245,15 @@ impl Agent for DataInventoryAgent {
                 // update gliders
                 let mut new_gliders = HashMap::<ItemId, Shared<DataInvItem>>::default();
                 for glider in data.gliders.values() {
+                    let ladder = data.point_ladder(glider).cloned().unwrap_or_default();
                     let new_glider = if let Some(i) = state.gliders.remove(&glider.id) {
                         i.write().unwrap().data = glider.clone();
+                        i.write().unwrap().ladder = ladder;
                         i
                     } else {
                         Rc::new(RwLock::new(DataInvItem {
                             data: glider.clone(),
+                            ladder,
                             inv: None,
                             stats: None,
                         }))
@@ -300,12 +262,31 @@ impl Agent for DataInventoryAgent {
                 }
                 state.gliders = new_gliders;
 
-                self.inventory.send(InventoryRequest::Refresh);
+                state.current_tour = data.current_tour(Utc::now()).cloned();
+                state.data = data.clone();
+                state.update_tour_data();
+                state.data_changed = true;
+
+                // renamed items are resolved before refreshing
+                drop(state);
+                drop(borrowed);
+                self.inventory.send(InventoryRequest::Resolve(store));
             }
             Msg::Inventory(inv) => {
                 let inv = &inv.borrow().inv;
                 let mut state = self.state.write().unwrap();
 
+                let changes = changed_items(&state.inv, inv);
+                if !state.data_changed && changes.len() <= INCREMENTAL_UPDATE_LIMIT {
+                    state.inv = inv.clone();
+                    for id in &changes {
+                        state.update_item_stats(id);
+                    }
+                    self.link.send_input(DataInventoryRequest::RefreshAll);
+                    return;
+                }
+                state.data_changed = false;
+
                 // update drivers
                 for driver in state.drivers.values() {
                     let mut driver = driver.write().unwrap();
@@ -336,6 +317,7 @@ impl Agent for DataInventoryAgent {
                     }
                 }
 
+                state.inv = inv.clone();
                 state.update_stats();
 
                 self.link.send_input(DataInventoryRequest::RefreshAll);
@@ -351,6 +333,14 @@ impl Agent for DataInventoryAgent {
                     self.link.respond(*handler, self.state.clone());
                 }
             }
+            DataInventoryRequest::ToggleTour => {
+                let mut state = self.state.write().unwrap();
+                state.tour_only = !state.tour_only;
+                state.update_tour_data();
+                state.update_stats();
+                drop(state);
+                self.link.send_input(DataInventoryRequest::RefreshAll);
+            }
         }
     }
 
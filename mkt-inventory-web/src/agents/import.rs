@@ -9,6 +9,7 @@ use yew_agent::{
 };
 
 use super::inventory::{Inventory, InventoryRequest};
+use crate::storage;
 
 pub enum Msg {
     UpdateInventory(MktInventory),
@@ -51,7 +52,7 @@ impl Agent for ImportAgent {
                 self.inventory.send(InventoryRequest::Add(Box::from(inv)));
             }
             Msg::UpdateHashes(new_hash) => {
-                let hash: Option<MktItemHashes> = LocalStorage::get("mkt_hash").ok();
+                let hash = storage::get_hashes();
                 // update local hashes
                 if !new_hash.hashes.is_empty() {
                     let mut hash = hash.unwrap_or_default();
@@ -65,8 +66,8 @@ impl Agent for ImportAgent {
     fn handle_input(&mut self, msg: Self::Input, id: yew_agent::HandlerId) {
         match msg {
             ImportRequest::ImportScreenshot(bytes) => {
-                let hash = LocalStorage::get("mkt_hash").ok();
-                let data = LocalStorage::get("mkt_data").ok();
+                let hash = storage::get_hashes();
+                let data = storage::get_data();
 
                 let (inv, new_hash) = screenshot::image_bytes_to_inventory(
                     bytes,
@@ -78,7 +79,7 @@ impl Agent for ImportAgent {
                 self.link.send_message(Msg::UpdateHashes(new_hash));
             }
             ImportRequest::BootstrapItemHashes(i_type, bytes) => {
-                let data = LocalStorage::get("mkt_data").ok();
+                let data = storage::get_data();
 
                 match screenshot::images_bytes_to_bootstrap_hashes(
                     bytes,
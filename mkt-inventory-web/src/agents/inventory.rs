@@ -1,29 +1,107 @@
+use chrono::Utc;
 use gloo::storage::{LocalStorage, Storage};
-use mkt_data::{ItemId, ItemType, MktInventory, OwnedItem};
+use mkt_data::{
+    diff::InventoryDiff,
+    history::MktInventoryHistory,
+    merge::{InventoryMerge, MergeConflict},
+    ItemId, ItemType, MktInventory, OwnedItem, ResolvedItems,
+};
 use yew_agent::{
-    utils::store::{Store, StoreWrapper},
+    utils::store::{ReadOnly, Store, StoreWrapper},
     AgentLink,
 };
 
+use super::data::DataStore;
+use crate::storage;
+
 pub enum Msg {
     Replace(Box<MktInventory>),
     Merge(Box<MktInventory>),
-    Refresh,
+    Import(Box<MktInventory>),
     RemoveItem(ItemId),
+    Resolve(ReadOnly<DataStore>),
+    Save,
+    Clear,
 }
 
+// older changes are compacted past that, the storage quota is small
+const MAX_HISTORY_CHANGES: usize = 10_000;
+
 pub enum InventoryRequest {
     Add(Box<MktInventory>),
+    // merged with the inventory at the last export or import
+    Import(Box<MktInventory>),
     Load,
     Save,
-    Refresh,
     Delete,
     AddItem(ItemType, OwnedItem),
     RemoveItem(ItemId),
+    Resolve(ReadOnly<DataStore>),
+}
+
+// what an import changed, for the import screen
+pub struct InventoryImport {
+    pub diff: InventoryDiff,
+    pub conflicts: Vec<MergeConflict>,
 }
 
 pub struct Inventory {
     pub inv: MktInventory,
+    // every import since the app started, in order
+    pub imports: Vec<InventoryImport>,
+    // loaded once, only the changes are recorded on each save
+    history: MktInventoryHistory,
+    // the data store, to rename the items of loaded and imported inventories
+    data: Option<ReadOnly<DataStore>>,
+}
+
+impl Inventory {
+    // returns true when items were renamed
+    fn resolve_items(&self, inv: &mut MktInventory) -> bool {
+        let data = match &self.data {
+            Some(data) => data.borrow(),
+            None => return false,
+        };
+        let ResolvedItems {
+            remapped,
+            unresolved,
+        } = inv.resolve_items(&data.data);
+        for (old_id, new_id) in &remapped {
+            gloo::console::log!(format!("renamed item {} to {}", old_id, new_id));
+        }
+        for id in unresolved {
+            gloo::console::warn!(format!("unknown item {}", id));
+        }
+        !remapped.is_empty()
+    }
+
+    fn save(&mut self) {
+        if let Err(e) = LocalStorage::set("mkt_inventory", &self.inv) {
+            gloo::console::error!(format!("inventory not saved: {}", e));
+            return;
+        }
+
+        if !self.history.record(&self.inv, Utc::now()) {
+            return;
+        }
+        let len = self.history.changes.len();
+        if len > MAX_HISTORY_CHANGES {
+            let date = self.history.changes[len - MAX_HISTORY_CHANGES].date;
+            self.history.compact(date);
+        }
+        // make room by dropping the older half of the history
+        while let Err(e) = LocalStorage::set("mkt_inventory_history", &self.history) {
+            let len = self.history.changes.len();
+            if let Some(date) = self.history.changes.get(len / 2).map(|c| c.date) {
+                self.history.compact(date);
+            }
+            if self.history.changes.len() == len {
+                gloo::console::error!(format!("inventory history not saved: {}", e));
+                break;
+            }
+            gloo::console::warn!(format!("inventory history compacted: {}", e));
+        }
+    }
 }
 
 impl Store for Inventory {
@@ -32,7 +110,13 @@ impl Store for Inventory {
 
     fn new() -> Self {
         let inv = MktInventory::new();
-        Self { inv }
+        let history = storage::get_history().unwrap_or_default();
+        Self {
+            inv,
+            imports: vec![],
+            history,
+            data: None,
+        }
     }
 
     fn handle_input(&self, link: AgentLink<StoreWrapper<Self>>, msg: Self::Input) {
@@ -41,19 +125,14 @@ impl Store for Inventory {
                 link.send_message(Msg::Merge(inv));
                 link.send_input(InventoryRequest::Save);
             }
+            InventoryRequest::Import(inv) => link.send_message(Msg::Import(inv)),
             InventoryRequest::Load => {
-                if let Ok(inv) = LocalStorage::get("mkt_inventory") {
-                    link.send_message(Msg::Replace(inv));
+                if let Some(inv) = storage::get_inventory() {
+                    link.send_message(Msg::Replace(Box::new(inv)));
                 }
             }
-            InventoryRequest::Save => {
-                LocalStorage::set("mkt_inventory", &self.inv).unwrap();
-            }
-            InventoryRequest::Refresh => link.send_message(Msg::Refresh),
-            InventoryRequest::Delete => {
-                link.send_message(Msg::Replace(Box::new(MktInventory::new())));
-                link.send_input(InventoryRequest::Save);
-            }
+            InventoryRequest::Save => link.send_message(Msg::Save),
+            InventoryRequest::Delete => link.send_message(Msg::Clear),
             InventoryRequest::AddItem(i_type, item) => {
                 let inv = MktInventory::from_item(i_type, item);
                 link.send_input(InventoryRequest::Add(Box::new(inv)));
@@ -62,21 +141,54 @@ impl Store for Inventory {
                 link.send_message(Msg::RemoveItem(id));
                 link.send_input(InventoryRequest::Save);
             }
+            InventoryRequest::Resolve(data) => link.send_message(Msg::Resolve(data)),
         }
     }
 
     fn reduce(&mut self, msg: Self::Action) {
         match msg {
-            Msg::Replace(inv) => {
+            Msg::Replace(mut inv) => {
+                self.resolve_items(&mut inv);
                 self.inv = *inv;
             }
-            Msg::Merge(inv) => {
+            Msg::Merge(mut inv) => {
+                self.resolve_items(&mut inv);
                 self.inv.update_inventory(*inv);
             }
-            Msg::Refresh => { /* do nothing */ }
+            // merged with the current inventory, even the edits not saved yet
+            Msg::Import(mut inv) => {
+                self.resolve_items(&mut inv);
+                let base = storage::get_sync_base();
+                let InventoryMerge {
+                    inventory,
+                    conflicts,
+                } = self.inv.merge_three_way(base.as_ref(), &inv);
+                let diff = self.inv.diff(&inventory);
+                storage::set_sync_base(&inventory);
+                self.inv = inventory;
+                self.imports.push(InventoryImport { diff, conflicts });
+                self.save();
+            }
             Msg::RemoveItem(id) => {
                 self.inv.remove_item(&id);
             }
+            // the whole inventory is only resolved when the data changes
+            Msg::Resolve(data) => {
+                self.data = Some(data);
+                let mut inv = std::mem::take(&mut self.inv);
+                let resolved = self.resolve_items(&mut inv);
+                self.inv = inv;
+                if resolved {
+                    self.save();
+                }
+            }
+            Msg::Save => self.save(),
+            Msg::Clear => {
+                self.inv = MktInventory::new();
+                self.history = MktInventoryHistory::new();
+                LocalStorage::delete("mkt_inventory");
+                LocalStorage::delete("mkt_inventory_history");
+            }
         }
     }
 }
@@ -60,7 +60,7 @@ impl Agent for UpdateAgent {
         match msg {
             UpdateRequest::CheckUpdateData => {
                 self.link.send_future(async move {
-                    let date = UpdateAgent::get_last_modified_date("mkt_data.json")
+                    let date = UpdateAgent::get_last_modified_date("mkt_data.json.gz")
                         .await
                         .unwrap_or_else(Utc::now);
                     if date
@@ -86,11 +86,25 @@ impl Agent for UpdateAgent {
 impl UpdateAgent {
     pub async fn load_data() -> Option<MktData> {
         let base = Url::parse(&gloo_utils::window().origin()).ok()?;
-        let mut url = base.join("MKT-Blanket/mkt_data.json").ok()?;
+        let mut url = base.join("MKT-Blanket/mkt_data.json.gz").ok()?;
         url.set_query(Some(&format!("day={}", Utc::now().date_naive())));
         let resp = reqwest::get(url).await.ok()?;
-        let json = resp.text().await.ok()?;
-        MktData::from_json(&json).ok()
+        let bundle = resp.bytes().await.ok()?;
+        let data = match MktData::from_bundle(&bundle) {
+            Ok(data) => data,
+            Err(e) => {
+                gloo::console::error!(format!("invalid mkt data bundle: {}", e));
+                return None;
+            }
+        };
+
+        // don't replace good data with broken data
+        let report = data.validate();
+        if !report.is_ok() {
+            gloo::console::error!(format!("invalid mkt data:\n{}", report));
+            return None;
+        }
+        Some(data)
     }
 
     pub async fn get_last_modified_date(file: &str) -> Option<DateTime<Utc>> {
@@ -1,6 +1,11 @@
 use gloo::events::EventListener;
 use itertools::Itertools;
-use mkt_data::{item_type_from_id, CourseId, ItemType};
+use mkt_data::{
+    coverage::CoverageLevel,
+    item_type_from_id,
+    score::{best_loadout, BestLoadout},
+    CourseId, ItemType,
+};
 use yew::prelude::*;
 use yew_agent::{Bridge, Bridged};
 
@@ -8,7 +13,7 @@ use crate::{
     agents::data_inventory::{
         DataInvCourse, DataInventory, DataInventoryAgent, DataInventoryRequest, Shared,
     },
-    comps::item::Item,
+    comps::item::{Item, ShowStat},
 };
 
 use super::modal_popup::*;
@@ -30,6 +35,9 @@ pub struct Props {
 
 pub struct Course {
     course: Option<Shared<DataInvCourse>>,
+    // only computed while the modal is open
+    loadout: BestLoadout,
+    state: Option<Shared<DataInventory>>,
     visible: bool,
     popup_listener: Option<EventListener>,
     data_inventory: Box<dyn Bridge<DataInventoryAgent>>,
@@ -43,6 +51,8 @@ impl Component for Course {
         let callback = ctx.link().callback(Msg::DataInventory);
         Self {
             course: None,
+            loadout: BestLoadout::default(),
+            state: None,
             visible: false,
             popup_listener: None,
             data_inventory: DataInventoryAgent::bridge(callback),
@@ -54,11 +64,13 @@ impl Component for Course {
             Msg::ToggleModal => {
                 self.visible = !self.visible;
                 self.popup_listener = update_popup_layer(self.visible, ctx, Msg::ToggleModal);
+                self.update_loadout(ctx);
                 true
             }
             Msg::DataInventory(state) => {
-                let state = state.read().unwrap();
-                self.course = state.courses.get(&ctx.props().id).cloned();
+                self.course = state.read().unwrap().courses.get(&ctx.props().id).cloned();
+                self.state = Some(state);
+                self.update_loadout(ctx);
                 true
             }
         }
@@ -80,28 +92,15 @@ impl Component for Course {
                         <span class="icon is-small ml-auto">
                             {
                                 if let Some(stats) = &course.stats {
-                                    let count = match ctx.props().i_type {
-                                        None => stats.driver_count + stats.kart_count + stats.glider_count,
-                                        Some(ItemType::Driver) => stats.driver_count,
-                                        Some(ItemType::Kart) => stats.kart_count,
-                                        Some(ItemType::Glider) => stats.glider_count,
-                                    };
-                                    let owned_count = match ctx.props().i_type {
-                                        None => stats.driver_owned_count + stats.kart_owned_count + stats.glider_owned_count,
-                                        Some(ItemType::Driver) => stats.driver_owned_count,
-                                        Some(ItemType::Kart) => stats.kart_owned_count,
-                                        Some(ItemType::Glider) => stats.glider_owned_count,
-                                    };
-                                    let covered = match ctx.props().i_type {
-                                        None => stats.driver_owned_count > 0 && stats.kart_owned_count > 0 && stats.glider_owned_count > 0,
-                                        Some(ItemType::Driver) => stats.driver_owned_count > 0,
-                                        Some(ItemType::Kart) => stats.kart_owned_count > 0,
-                                        Some(ItemType::Glider) => stats.glider_owned_count > 0,
-                                    };
+                                    let count = stats.count(ctx.props().i_type);
+                                    let owned_count = stats.owned_count(ctx.props().i_type);
+                                    let coverage = stats.coverage(ctx.props().i_type);
                                     if owned_count == count {
                                         html! {<i class="fas fa-star has-text-success"></i>}
-                                    } else if covered {
+                                    } else if coverage == CoverageLevel::Favorite {
                                         html! {<i class="fas fa-check has-text-success"></i>}
+                                    } else if coverage == CoverageLevel::Favored {
+                                        html! {<i class="fas fa-check has-text-warning"></i>}
                                     } else if owned_count == 0 {
                                         html! {<i class="fas fa-times has-text-danger"></i>}
                                     } else {
@@ -131,6 +130,17 @@ impl Component for Course {
 }
 
 impl Course {
+    fn update_loadout(&mut self, ctx: &Context<Self>) {
+        self.loadout = match &self.state {
+            Some(state) if self.visible => {
+                let state = state.read().unwrap();
+                // two alternates, for items already used in the same cup
+                best_loadout(&state.data, &state.inv, &ctx.props().id, 2)
+            }
+            _ => BestLoadout::default(),
+        };
+    }
+
     fn view_course_modal(&self, ctx: &Context<Self>) -> Html {
         if self.visible {
             if let Some(course) = &self.course {
@@ -156,8 +166,28 @@ impl Course {
                     .filter(|r| item_type_from_id(&r.id) == Some(ItemType::Glider))
                     .collect_vec();
                 gliders.sort_by_key(|i| &i.id);
+                let loadout = [ItemType::Driver, ItemType::Kart, ItemType::Glider]
+                    .iter()
+                    .filter_map(|i_type| self.loadout.items(*i_type).split_first())
+                    .collect_vec();
                 let items = html! {
                     <>
+                    if !loadout.is_empty() {
+                        <p class="subtitle is-6">{"Best Owned Loadout"}</p>
+                        <div class="columns is-multiline">
+                        { for loadout.iter().map(|(best, alternates)| html!{
+                            <>
+                            <div class="column is-full py-1"><Item id={best.id.clone()} show_stat={ShowStat::LevelPoints} /></div>
+                            if !alternates.is_empty() {
+                                <div class="column is-full py-1 pl-5">
+                                    <p class="is-size-7">{"Alternates"}</p>
+                                    { for alternates.iter().map(|a| html!{ <div class="py-1"><Item id={a.id.clone()} show_stat={ShowStat::LevelPoints} /></div> }) }
+                                </div>
+                            }
+                            </>
+                        }) }
+                        </div>
+                    }
                     <p class="subtitle is-6">{"Drivers"}</p>
                     <div class="columns is-multiline">
                     { for drivers.iter().map(|r| html!{ <div class="column is-full py-1"><Item id={r.id.clone()} lvl_req={r.lvl} /></div> }) }
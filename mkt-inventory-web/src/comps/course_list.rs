@@ -1,16 +1,21 @@
 use itertools::Itertools;
-use mkt_data::{course_generation_from_id, course_parts_from_id, course_type_from_id, CourseType};
+use mkt_data::{
+    course_generation_from_id, course_parts_from_id, course_type_from_id, CourseType, Tour,
+};
 use yew::prelude::*;
 use yew_agent::{Bridge, Bridged};
 
 use crate::{
-    agents::data_inventory::{DataInvCourse, DataInventory, DataInventoryAgent, Shared},
+    agents::data_inventory::{
+        DataInvCourse, DataInventory, DataInventoryAgent, DataInventoryRequest, Shared,
+    },
     comps::course::Course,
 };
 
 pub enum Msg {
     DataInventory(Shared<DataInventory>),
     _Toggle,
+    ToggleTour,
 }
 
 #[derive(Properties, Clone, PartialEq)]
@@ -19,7 +24,9 @@ pub struct Props {}
 pub struct CourseList {
     courses: Vec<Shared<DataInvCourse>>,
     visible: bool,
-    _data_inventory: Box<dyn Bridge<DataInventoryAgent>>,
+    current_tour: Option<Tour>,
+    tour_only: bool,
+    data_inventory: Box<dyn Bridge<DataInventoryAgent>>,
 }
 
 impl Component for CourseList {
@@ -31,7 +38,9 @@ impl Component for CourseList {
         Self {
             courses: Vec::new(),
             visible: true,
-            _data_inventory: DataInventoryAgent::bridge(callback),
+            current_tour: None,
+            tour_only: false,
+            data_inventory: DataInventoryAgent::bridge(callback),
         }
     }
 
@@ -39,26 +48,37 @@ impl Component for CourseList {
         match msg {
             Msg::DataInventory(state) => {
                 let state = state.read().unwrap();
+                let tour_changed =
+                    state.current_tour != self.current_tour || state.tour_only != self.tour_only;
+                self.current_tour = state.current_tour.clone();
+                self.tour_only = state.tour_only;
                 if state.courses.len() != self.courses.len() {
                     self.courses = state.courses.values().cloned().collect();
                     self.courses
                         .sort_by_key(|c| course_parts_from_id(&c.read().unwrap().data.id));
                     true
                 } else {
-                    false
+                    tour_changed
                 }
             }
             Msg::_Toggle => {
                 self.visible = !self.visible;
                 true
             }
+            // the stats are scoped by the agent, the list follows
+            Msg::ToggleTour => {
+                self.data_inventory.send(DataInventoryRequest::ToggleTour);
+                false
+            }
         }
     }
 
-    fn view(&self, _ctx: &Context<Self>) -> Html {
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        // only the courses in rotation this week
+        let tour = self.current_tour.as_ref().filter(|_| self.tour_only);
         let courses = if self.visible {
             html! {
-                { for self.courses.iter().group_by(|c| course_generation_from_id(&c.read().unwrap().data.id)).into_iter().map(|(gen, cs)| {
+                { for self.courses.iter().filter(|c| tour.map(|t| t.courses.contains(&c.read().unwrap().data.id)).unwrap_or(true)).group_by(|c| course_generation_from_id(&c.read().unwrap().data.id)).into_iter().map(|(gen, cs)| {
                     let mut expected = 0;
                     html! {
                         <>
@@ -96,6 +116,18 @@ impl Component for CourseList {
         html! {
             <>
                 <h2 class="title is-4">{"Coverage"}</h2>
+                { if let Some(tour) = &self.current_tour {
+                    html! {
+                        <div class="block">
+                            <button class={classes!("button", "is-small", self.tour_only.then_some("is-info"))} onclick={ctx.link().callback(|_| Msg::ToggleTour)}>
+                                <span class="icon"><i class="fas fa-filter"/></span>
+                                <span>{ format!("{} only", tour.name) }</span>
+                            </button>
+                        </div>
+                    }
+                } else {
+                    html! {}
+                }}
                 <div class="block">
                     { courses }
                 </div>
@@ -1,7 +1,7 @@
-use gloo::storage::{LocalStorage, Storage};
-use mkt_data::MktData;
 use yew::prelude::*;
 
+use crate::storage;
+
 use super::data_manager::download_file;
 
 #[derive(Clone)]
@@ -25,8 +25,7 @@ impl Component for DownloadData {
     fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::Download => {
-                if let Ok(data) = LocalStorage::get("mkt_data") {
-                    let data: MktData = data;
+                if let Some(data) = storage::get_data() {
                     let json = serde_json::to_string_pretty(&data).unwrap();
                     download_file("mkt_data.json", json.as_str());
                 }
@@ -1,8 +1,8 @@
-use gloo::storage::{LocalStorage, Storage};
-use mkt_data::MktData;
 use mkt_data::MktItemHashes;
 use yew::prelude::*;
 
+use crate::storage;
+
 use super::data_manager::download_file;
 
 #[derive(Clone)]
@@ -27,10 +27,9 @@ impl Component for DownloadHash {
     fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::DownloadAll => {
-                if let Ok(data) = LocalStorage::get("mkt_data") {
-                    let data: MktData = data;
+                if let Some(data) = storage::get_data() {
                     let mut data_hash = data.hashes();
-                    if let Ok(hash) = LocalStorage::get("mkt_hash") {
+                    if let Some(hash) = storage::get_hashes() {
                         data_hash.merge(hash);
                     };
                     let json = serde_json::to_string_pretty(&data_hash).unwrap();
@@ -40,7 +39,7 @@ impl Component for DownloadHash {
             }
             Msg::DownloadPersonal => {
                 let mut data_hash = MktItemHashes::new();
-                if let Ok(hash) = LocalStorage::get("mkt_hash") {
+                if let Some(hash) = storage::get_hashes() {
                     data_hash.merge(hash);
                 };
                 let json = serde_json::to_string_pretty(&data_hash).unwrap();
@@ -1,12 +1,13 @@
-use gloo::storage::{LocalStorage, Storage};
-use mkt_data::MktInventory;
 use yew::prelude::*;
 
+use crate::storage;
+
 use super::data_manager::download_file;
 
 #[derive(Clone)]
 pub enum Msg {
     Download,
+    DownloadHistory,
 }
 
 #[derive(Properties, Clone, PartialEq)]
@@ -25,10 +26,18 @@ impl Component for DownloadInv {
     fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::Download => {
-                if let Ok(inv) = LocalStorage::get("mkt_inventory") {
-                    let inv: MktInventory = inv;
+                if let Some(inv) = storage::get_inventory() {
                     let json = serde_json::to_string_pretty(&inv).unwrap();
                     download_file("mkt_inventory.json", json.as_str());
+                    // the next import is merged from this export
+                    storage::set_sync_base(&inv);
+                }
+                false
+            }
+            Msg::DownloadHistory => {
+                if let Some(history) = storage::get_history() {
+                    let json = history.to_json().unwrap();
+                    download_file("mkt_inventory_history.json", json.as_str());
                 }
                 false
             }
@@ -42,6 +51,10 @@ impl Component for DownloadInv {
                     <span class="icon"><i class="fas fa-download"/></span>
                     <span>{ "Download Inventory" }</span>
                 </button>
+                <button class={classes!("button", "is-info", "ml-2")} onclick={ctx.link().callback(|_| Msg::DownloadHistory)}>
+                    <span class="icon"><i class="fas fa-download"/></span>
+                    <span>{ "Download History" }</span>
+                </button>
             </>
         }
     }
@@ -1,10 +1,18 @@
-use mkt_data::ItemType;
+use mkt_data::{ItemLvl, ItemType};
+use mkt_import::sheet::sheet_to_inventory;
 use wasm_bindgen::prelude::*;
+use web_sys::HtmlTextAreaElement;
 use yew::prelude::*;
-use yew_agent::{Bridge, Bridged};
+use yew_agent::{
+    utils::store::{Bridgeable, StoreWrapper},
+    Bridge, Bridged,
+};
 
-use crate::agents::data_inventory::{
-    DataInvItem, DataInventory, DataInventoryAgent, DataInventoryRequest, Shared,
+use crate::agents::{
+    data_inventory::{
+        DataInvItem, DataInventory, DataInventoryAgent, DataInventoryRequest, Shared,
+    },
+    inventory::{Inventory, InventoryRequest},
 };
 
 #[wasm_bindgen(module = "/js/utils.js")]
@@ -14,6 +22,8 @@ extern "C" {
 
 pub enum Msg {
     CopyBgr,
+    Sheet(String),
+    ImportBgr,
     DataInventory(Shared<DataInventory>),
     _ToggleDisplay,
 }
@@ -21,7 +31,13 @@ pub enum Msg {
 pub struct ImportExportBgr {
     items: Vec<Shared<DataInvItem>>,
     visible: bool,
+    // pasted rows of the BGR sheet
+    sheet: String,
+    unknown: Vec<String>,
+    invalid: Vec<(String, ItemLvl)>,
+    state: Option<Shared<DataInventory>>,
     data_inventory: Box<dyn Bridge<DataInventoryAgent>>,
+    inventory: Box<dyn Bridge<StoreWrapper<Inventory>>>,
 }
 
 impl Component for ImportExportBgr {
@@ -33,14 +49,20 @@ impl Component for ImportExportBgr {
         Self {
             items: Vec::new(),
             visible: true,
+            sheet: String::new(),
+            unknown: Vec::new(),
+            invalid: Vec::new(),
+            state: None,
             data_inventory: DataInventoryAgent::bridge(callback),
+            inventory: Inventory::bridge(Callback::noop()),
         }
     }
 
     fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
-            Msg::DataInventory(state) => {
-                let state = state.read().unwrap();
+            Msg::DataInventory(shared) => {
+                self.state = Some(shared.clone());
+                let state = shared.read().unwrap();
                 if state.drivers.len() + state.karts.len() + state.gliders.len() != self.items.len()
                 {
                     self.items = state
@@ -58,6 +80,21 @@ impl Component for ImportExportBgr {
                     false
                 }
             }
+            Msg::Sheet(sheet) => {
+                self.sheet = sheet;
+                false
+            }
+            Msg::ImportBgr => {
+                if let Some(state) = &self.state {
+                    let state = state.read().unwrap();
+                    let (inv, unknown, invalid) =
+                        sheet_to_inventory(&self.sheet, &state.data, &state.inv);
+                    self.unknown = unknown;
+                    self.invalid = invalid;
+                    self.inventory.send(InventoryRequest::Add(Box::new(inv)));
+                }
+                true
+            }
             Msg::_ToggleDisplay => {
                 self.visible = !self.visible;
                 true
@@ -79,7 +116,7 @@ impl Component for ImportExportBgr {
                         i.inv.as_ref().map(|n| n.lvl).unwrap_or(0),
                         i.inv
                             .as_ref()
-                            .map(|n| n.point_cap_tier(&i.data))
+                            .map(|n| n.point_cap_tier(&i.ladder))
                             .unwrap_or(0)
                     )
                     .unwrap();
@@ -99,12 +136,30 @@ impl Component for ImportExportBgr {
     fn view(&self, ctx: &Context<Self>) -> Html {
         let bgr = if self.visible {
             html! {
+                <>
                 <div class="block">
                 <button class={classes!("button", "is-info")} onclick={ctx.link().callback(|_| Msg::CopyBgr)}>
                     <span class="icon"><i class="fas fa-copy"/></span>
                     <span>{ "Copy BGR Sheet to clipboard" }</span>
                 </button>
                 </div>
+                <div class="block">
+                <textarea class="textarea" placeholder="Paste the BGR Sheet rows, names in any language" oninput={ctx.link().callback(|e: InputEvent| {
+                    let input: HtmlTextAreaElement = e.target_unchecked_into();
+                    Msg::Sheet(input.value())
+                })} />
+                <button class={classes!("button", "is-info", "mt-2")} onclick={ctx.link().callback(|_| Msg::ImportBgr)}>
+                    <span class="icon"><i class="fas fa-upload"/></span>
+                    <span>{ "Import BGR Sheet" }</span>
+                </button>
+                { for self.unknown.iter().map(|n| html! {
+                    <p class="help is-warning">{ format!("Unknown item {}", n) }</p>
+                }) }
+                { for self.invalid.iter().map(|(n, lvl)| html! {
+                    <p class="help is-warning">{ format!("Invalid level {} for {}", lvl, n) }</p>
+                }) }
+                </div>
+                </>
             }
         } else {
             html! {}
@@ -1,17 +1,25 @@
 use gloo::{
     file::{self, callbacks::FileReader, File},
-    storage::{LocalStorage, Storage},
     timers::callback::Timeout,
 };
-use mkt_data::MktInventory;
+use mkt_data::{
+    merge::{MergeConflict, MergeSide},
+    MktData, MktInventory, OwnedItem,
+};
 use web_sys::HtmlInputElement;
 use yew::prelude::*;
 use yew_agent::{
-    utils::store::{Bridgeable, StoreWrapper},
-    Bridge,
+    utils::store::{Bridgeable, ReadOnly, StoreWrapper},
+    Bridge, Bridged,
 };
 
-use crate::agents::inventory::{Inventory, InventoryRequest};
+use crate::{
+    agents::{
+        data_inventory::{DataInventory, DataInventoryAgent, Shared},
+        inventory::{Inventory, InventoryRequest},
+    },
+    storage,
+};
 
 use super::data_manager::download_file;
 
@@ -20,6 +28,8 @@ pub enum Msg {
     Loaded(String, String),
     Done,
     Download,
+    DataInventory(Shared<DataInventory>),
+    Inventory(ReadOnly<Inventory>),
 }
 
 #[derive(Properties, Clone, PartialEq)]
@@ -29,25 +39,43 @@ pub struct ImportExportInv {
     readers: Vec<FileReader>,
     completed: usize,
     timeout: Option<Timeout>,
+    errors: Vec<String>,
+    conflicts: Vec<String>,
+    changes: String,
+    state: Option<Shared<DataInventory>>,
+    // imports of the store already shown, none before the first update
+    seen_imports: Option<usize>,
     pub inventory: Box<dyn Bridge<StoreWrapper<Inventory>>>,
+    _data_inventory: Box<dyn Bridge<DataInventoryAgent>>,
 }
 
 impl Component for ImportExportInv {
     type Message = Msg;
     type Properties = Props;
 
-    fn create(_ctx: &Context<Self>) -> Self {
+    fn create(ctx: &Context<Self>) -> Self {
+        let callback = ctx.link().callback(Msg::DataInventory);
+        let callback_inv = ctx.link().callback(Msg::Inventory);
         Self {
             readers: vec![],
             completed: 0,
             timeout: None,
-            inventory: Inventory::bridge(Callback::noop()),
+            errors: vec![],
+            conflicts: vec![],
+            changes: String::new(),
+            state: None,
+            seen_imports: None,
+            inventory: Inventory::bridge(callback_inv),
+            _data_inventory: DataInventoryAgent::bridge(callback),
         }
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::Files(files) => {
+                self.errors.clear();
+                self.conflicts.clear();
+                self.changes.clear();
                 for file in files.into_iter() {
                     let task = {
                         let file_name = file.name();
@@ -61,9 +89,11 @@ impl Component for ImportExportInv {
                 }
                 true
             }
-            Msg::Loaded(_file_name, json) => {
-                if let Ok(inv) = MktInventory::from_json(&json) {
-                    self.inventory.send(InventoryRequest::Add(Box::from(inv)));
+            Msg::Loaded(file_name, json) => {
+                // the store merges it, the changes come back with the inventory
+                match MktInventory::from_json(&json) {
+                    Ok(inv) => self.inventory.send(InventoryRequest::Import(Box::new(inv))),
+                    Err(error) => self.errors.push(format!("{}: {}", file_name, error)),
                 }
 
                 self.completed += 1;
@@ -82,11 +112,34 @@ impl Component for ImportExportInv {
                 self.timeout = None;
                 true
             }
+            Msg::DataInventory(state) => {
+                self.state = Some(state);
+                false
+            }
+            Msg::Inventory(store) => {
+                let store = store.borrow();
+                let seen = self.seen_imports.unwrap_or(store.imports.len());
+                self.seen_imports = Some(store.imports.len());
+                let state = match &self.state {
+                    Some(state) => state.read().unwrap(),
+                    None => return false,
+                };
+                for import in &store.imports[seen..] {
+                    self.changes += &import.diff.to_text(&state.data);
+                    self.conflicts.extend(
+                        import
+                            .conflicts
+                            .iter()
+                            .map(|c| conflict_text(&state.data, c)),
+                    );
+                }
+                seen < store.imports.len()
+            }
             Msg::Download => {
-                if let Ok(inv) = LocalStorage::get("mkt_inventory") {
-                    let inv: MktInventory = inv;
+                if let Some(inv) = storage::get_inventory() {
                     let json = serde_json::to_string_pretty(&inv).unwrap();
                     download_file("mkt_inventory.json", json.as_str());
+                    storage::set_sync_base(&inv);
                 }
                 false
             }
@@ -134,7 +187,7 @@ impl Component for ImportExportInv {
                     </div>
                 </p>
 
-                { if self.timeout.is_some() {
+                { if self.timeout.is_some() && self.errors.is_empty() {
                     html! {
                         <p class="control">{ "Inventory loaded!" }</p>
                     }
@@ -142,7 +195,42 @@ impl Component for ImportExportInv {
                     html! {}
                 }}
             </div>
+            { for self.errors.iter().map(|e| html! {
+                <p class="help is-danger">{ e }</p>
+            }) }
+            { for self.conflicts.iter().map(|c| html! {
+                <p class="help is-warning">{ c }</p>
+            }) }
+            { if !self.changes.is_empty() {
+                html! {
+                    <pre>{ &self.changes }</pre>
+                }
+            } else {
+                html! {}
+            }}
             </div>
         }
     }
 }
+
+// edited on both devices since the last export
+fn conflict_text(data: &MktData, conflict: &MergeConflict) -> String {
+    let name = data
+        .get_item(&conflict.id)
+        .map(|i| i.name.as_str())
+        .unwrap_or(&conflict.id);
+    let state = |item: &Option<OwnedItem>| match item {
+        Some(i) => format!("lvl {}, {} pts", i.lvl, i.points),
+        None => "removed".to_string(),
+    };
+    let (kept, other) = match conflict.kept {
+        MergeSide::Local => (&conflict.local, &conflict.incoming),
+        MergeSide::Incoming => (&conflict.incoming, &conflict.local),
+    };
+    format!(
+        "Conflict on {}: kept {} over {}",
+        name,
+        state(kept),
+        state(other)
+    )
+}
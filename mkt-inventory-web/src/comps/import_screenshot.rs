@@ -4,15 +4,19 @@ use gloo::{
     timers::callback::Timeout,
 };
 use itertools::Itertools;
-use mkt_data::ItemType;
+use mkt_data::{ItemType, MktInventory};
 use web_sys::HtmlInputElement;
 use yew::prelude::*;
-use yew_agent::{Bridge, Bridged};
+use yew_agent::{
+    utils::store::{Bridgeable, ReadOnly, StoreWrapper},
+    Bridge, Bridged,
+};
 
 use crate::{
     agents::{
         data_inventory::{DataInvItem, DataInventory, DataInventoryAgent, Shared},
         import::{ImportAgent, ImportRequest},
+        inventory::Inventory,
     },
     comps::item::ShowStat,
 };
@@ -24,6 +28,7 @@ pub enum Msg {
     Loaded(String, Vec<u8>),
     Done,
     DataInventory(Shared<DataInventory>),
+    Inventory(ReadOnly<Inventory>),
 }
 
 #[derive(Properties, Clone, PartialEq)]
@@ -35,8 +40,13 @@ pub struct ImportScreenshot {
     timeout: Option<Timeout>,
     last_changed: DateTime<Utc>,
     modified_items: Vec<Shared<DataInvItem>>,
+    // inventory before the import, to list the changes
+    before: Option<MktInventory>,
+    changes: String,
+    state: Option<Shared<DataInventory>>,
     import: Box<dyn Bridge<ImportAgent>>,
     _data_inventory: Box<dyn Bridge<DataInventoryAgent>>,
+    _inventory: Box<dyn Bridge<StoreWrapper<Inventory>>>,
 }
 
 impl Component for ImportScreenshot {
@@ -45,14 +55,19 @@ impl Component for ImportScreenshot {
 
     fn create(ctx: &Context<Self>) -> Self {
         let callback = ctx.link().callback(Msg::DataInventory);
+        let callback_inv = ctx.link().callback(Msg::Inventory);
         Self {
             readers: vec![],
             completed: 0,
             timeout: None,
             last_changed: Utc::now(),
             modified_items: Vec::new(),
+            before: None,
+            changes: String::new(),
+            state: None,
             import: ImportAgent::bridge(Callback::noop()),
             _data_inventory: DataInventoryAgent::bridge(callback),
+            _inventory: Inventory::bridge(callback_inv),
         }
     }
 
@@ -61,6 +76,8 @@ impl Component for ImportScreenshot {
             Msg::Files(files) => {
                 self.modified_items = vec![];
                 self.last_changed = Utc::now();
+                self.before = self.state.as_ref().map(|s| s.read().unwrap().inv.clone());
+                self.changes.clear();
                 for file in files.into_iter() {
                     let task = {
                         let file_name = file.name();
@@ -93,8 +110,9 @@ impl Component for ImportScreenshot {
                 self.timeout = None;
                 true
             }
-            Msg::DataInventory(state) => {
-                let state = state.read().unwrap();
+            Msg::DataInventory(shared) => {
+                self.state = Some(shared.clone());
+                let state = shared.read().unwrap();
                 self.modified_items = state
                     .drivers
                     .values()
@@ -110,6 +128,15 @@ impl Component for ImportScreenshot {
                     .sort_by_key(|i| i.read().unwrap().data.id.clone());
                 true
             }
+            Msg::Inventory(inv) => {
+                if let (Some(before), Some(state)) = (&self.before, &self.state) {
+                    let data = &state.read().unwrap().data;
+                    self.changes = before.diff(&inv.borrow().inv).to_text(data);
+                    true
+                } else {
+                    false
+                }
+            }
         }
     }
 
@@ -201,6 +228,13 @@ impl Component for ImportScreenshot {
             } else {
                 html! {}
             }}
+            { if !self.changes.is_empty() {
+                html! {
+                    <div class="block"><pre>{ &self.changes }</pre></div>
+                }
+            } else {
+                html! {}
+            }}
             </div>
         }
     }
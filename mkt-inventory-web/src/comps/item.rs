@@ -2,6 +2,7 @@ use gloo::events::EventListener;
 use itertools::Itertools;
 use mkt_data::{
     course_parts_from_id, item_type_from_id, ItemId, ItemLvl, ItemPoints, ItemType, OwnedItem,
+    SkillLvl,
 };
 use yew::prelude::*;
 use yew_agent::utils::store::{Bridgeable, StoreWrapper};
@@ -23,6 +24,7 @@ pub enum Msg {
     EditToggle,
     SetLevel(ItemLvl),
     SetPoints(ItemPoints),
+    SetSkillLevel(SkillLvl),
     IncrementPoints,
     DecrementPoints,
     AddItem,
@@ -151,12 +153,26 @@ impl Component for Item {
                 }
                 false
             }
+            Msg::SetSkillLevel(skill_lvl) => {
+                if let Some(item) = &self.item {
+                    if let Some(inv) = &item.read().unwrap().inv {
+                        if Some(skill_lvl) != inv.skill_lvl {
+                            let mut new_inv = OwnedItem::new(inv.id.clone(), inv.lvl, inv.points);
+                            new_inv.skill_lvl = Some(skill_lvl);
+                            self.inventory
+                                .send(InventoryRequest::AddItem(self.i_type.unwrap(), new_inv));
+                            return true;
+                        }
+                    }
+                }
+                false
+            }
             Msg::IncrementPoints => {
                 if let Some(item) = &self.item {
                     let item = item.read().unwrap();
                     if let Some(inv) = &item.inv {
                         let mut inv = inv.clone();
-                        inv.increment_points(&item.data);
+                        inv.increment_points(&item.ladder);
                         ctx.link().send_message(Msg::SetPoints(inv.points));
                     }
                 }
@@ -167,7 +183,7 @@ impl Component for Item {
                     let item = item.read().unwrap();
                     if let Some(inv) = &item.inv {
                         let mut inv = inv.clone();
-                        inv.decrement_points(&item.data);
+                        inv.decrement_points(&item.ladder);
                         ctx.link().send_message(Msg::SetPoints(inv.points));
                     }
                 }
@@ -179,7 +195,7 @@ impl Component for Item {
                     let new_inv = OwnedItem::new(
                         item.data.id.clone(),
                         *item.data.valid_levels().first().unwrap(),
-                        *item.data.valid_points().first().unwrap(),
+                        item.ladder.caps.first().copied().unwrap_or_default(),
                     );
                     self.inventory
                         .send(InventoryRequest::AddItem(self.i_type.unwrap(), new_inv));
@@ -324,7 +340,7 @@ impl Item {
                                 <div class="control">
                                     <div class="buttons">
                                         <button class={classes!("button", "is-small")} onclick={ctx.link().callback(|_| Msg::DecrementPoints)}><span class="icon"><i class="fas fa-minus"/></span></button>
-                                        { for item.data.points_cap_tiers().into_iter().map(|p| html! {
+                                        { for item.ladder.caps.iter().copied().map(|p| html! {
                                             <button class={classes!("button", (item.inv.as_ref().map(|i| i.points).unwrap_or_default() == p).then_some("is-info"))} onclick={ctx.link().callback(move |_| Msg::SetPoints(p))}>{p}</button>
                                         }) }
                                         <button class={classes!("button", "is-small")} onclick={ctx.link().callback(|_| Msg::IncrementPoints)}><span class="icon"><i class="fas fa-plus"/></span></button>
@@ -333,6 +349,22 @@ impl Item {
                             </div>
                         </div>
                     </div>
+                    <div class="field is-horizontal">
+                        <div class="field-label is-normal">
+                            <label>{"Skill:"}</label>
+                        </div>
+                        <div class="field-body">
+                            <div class="field is-narrow">
+                                <div class="control">
+                                    <div class="buttons">
+                                        { for item.data.valid_skill_levels().into_iter().map(|l| html! {
+                                            <button class={classes!("button", (item.inv.as_ref().and_then(|i| i.skill_lvl) == Some(l)).then_some("is-info"))} onclick={ctx.link().callback(move |_| Msg::SetSkillLevel(l))}>{l}</button>
+                                        }) }
+                                    </div>
+                                </div>
+                            </div>
+                        </div>
+                    </div>
                 </>
                 };
 
@@ -343,6 +375,13 @@ impl Item {
                                 <span style="display: inline-block;min-width: 5rem;">{"Level:"}<span class="stat-lvl-big">{inv.lvl}</span></span>
                                 <span>{"Points:"}<span class="stat-points-big">{inv.points}</span></span>
                             </div>
+                            { if let Some(skill) = &item.data.skill {
+                                let lvl = inv.skill_lvl.map(|l| format!(" Lvl. {}", l)).unwrap_or_default();
+                                let value = inv.skill_lvl.and_then(|l| skill.value(l)).map(|v| format!(" (+{})", v)).unwrap_or_default();
+                                html! { <p class="block">{"Skill: "}<b>{ &skill.name }</b>{ lvl }{ value }</p> }
+                            } else {
+                                html! {}
+                            }}
                             { if self.edit_visible { edit } else { html!{} }}
                             <div class="buttons">
                                 <button class={classes!("button")} onclick={ctx.link().callback(|_| Msg::EditToggle)}>
@@ -1,5 +1,6 @@
 use crate::agents::data_inventory::Shared;
 use crate::agents::data_inventory::{DataInventory, DataInventoryAgent};
+use mkt_data::ItemType;
 use yew::prelude::*;
 use yew_agent::{Bridge, Bridged};
 
@@ -16,6 +17,7 @@ pub struct Summary {
     kart_count: usize,
     glider_count: usize,
     course_covered_count: usize,
+    course_favored_count: usize,
     driver_owned_count: usize,
     kart_owned_count: usize,
     glider_owned_count: usize,
@@ -34,6 +36,7 @@ impl Component for Summary {
             kart_count: 0,
             glider_count: 0,
             course_covered_count: 0,
+            course_favored_count: 0,
             driver_owned_count: 0,
             kart_owned_count: 0,
             glider_owned_count: 0,
@@ -46,52 +49,22 @@ impl Component for Summary {
             Msg::DataInventory(state) => {
                 let state = state.read().unwrap();
 
-                self.course_count = state.courses.len();
-                self.course_covered_count = 0;
-                for course in state.courses.values() {
-                    let course = course.read().unwrap();
-                    if let Some(stats) = &course.stats {
-                        if stats.driver_owned_count > 0
-                            && stats.kart_owned_count > 0
-                            && stats.glider_owned_count > 0
-                        {
-                            self.course_covered_count += 1;
-                        }
-                    }
-                }
+                let courses = state.report.course_totals();
+                self.course_count = courses.course_count;
+                self.course_covered_count = courses.covered_count;
+                self.course_favored_count = courses.favored_count;
 
-                self.driver_count = state.drivers.len();
-                self.driver_owned_count = 0;
-                for driver in state.drivers.values() {
-                    let driver = driver.read().unwrap();
-                    if let Some(inv) = &driver.inv {
-                        if inv.lvl > 0 {
-                            self.driver_owned_count += 1;
-                        }
-                    }
-                }
+                let drivers = state.report.item_totals(ItemType::Driver);
+                self.driver_count = drivers.item_count;
+                self.driver_owned_count = drivers.owned_count;
 
-                self.kart_count = state.karts.len();
-                self.kart_owned_count = 0;
-                for kart in state.karts.values() {
-                    let kart = kart.read().unwrap();
-                    if let Some(inv) = &kart.inv {
-                        if inv.lvl > 0 {
-                            self.kart_owned_count += 1;
-                        }
-                    }
-                }
+                let karts = state.report.item_totals(ItemType::Kart);
+                self.kart_count = karts.item_count;
+                self.kart_owned_count = karts.owned_count;
 
-                self.glider_count = state.gliders.len();
-                self.glider_owned_count = 0;
-                for glider in state.gliders.values() {
-                    let glider = glider.read().unwrap();
-                    if let Some(inv) = &glider.inv {
-                        if inv.lvl > 0 {
-                            self.glider_owned_count += 1;
-                        }
-                    }
-                }
+                let gliders = state.report.item_totals(ItemType::Glider);
+                self.glider_count = gliders.item_count;
+                self.glider_owned_count = gliders.owned_count;
 
                 true
             }
@@ -117,6 +90,7 @@ impl Component for Summary {
                     <h3 class="title is-5">{ "Coverage Summary" }</h3>
                     <ul>
                         <li><b>{"Courses: "}</b>{ format!("{}/{} ({:.1}%)", self.course_covered_count, self.course_count, self.course_covered_count as f64 / self.course_count as f64 * 100.0) }</li>
+                        <li><b>{"Courses with favored items only: "}</b>{ format!("{}/{} ({:.1}%)", self.course_favored_count, self.course_count, self.course_favored_count as f64 / self.course_count as f64 * 100.0) }</li>
                         <li><b>{"Drivers: "}</b>{ format!("{}/{} ({:.1}%)", self.driver_owned_count, self.driver_count, self.driver_owned_count as f64 / self.driver_count as f64 * 100.0) }</li>
                         <li><b>{"Karts: "}</b>{ format!("{}/{} ({:.1}%)", self.kart_owned_count, self.kart_count, self.kart_owned_count as f64 / self.kart_count as f64 * 100.0) }</li>
                         <li><b>{"Gliders: "}</b>{ format!("{}/{} ({:.1}%)", self.glider_owned_count, self.glider_count, self.glider_owned_count as f64 / self.glider_count as f64 * 100.0) }</li>
@@ -5,6 +5,7 @@
 
 mod agents;
 mod comps;
+mod storage;
 
 use agents::{
     data::{DataRequest, DataStore},
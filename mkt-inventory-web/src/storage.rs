@@ -0,0 +1,52 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use gloo::storage::{LocalStorage, Storage};
+use mkt_data::{history::MktInventoryHistory, MktData, MktInventory, MktItemHashes};
+
+// stored documents are read through the mkt-data loaders, so older formats get migrated
+
+fn get_json(key: &str) -> Option<String> {
+    LocalStorage::raw().get_item(key).ok().flatten()
+}
+
+// the data is stored as a base64 bundle, older versions stored json
+pub fn get_data() -> Option<MktData> {
+    let stored = get_json("mkt_data")?;
+    if stored.starts_with('{') {
+        MktData::from_json(&stored).ok()
+    } else {
+        let bundle = STANDARD.decode(stored).ok()?;
+        MktData::from_bundle(&bundle).ok()
+    }
+}
+
+pub fn set_data(data: &MktData) {
+    if let Ok(bundle) = data.to_bundle() {
+        LocalStorage::raw()
+            .set_item("mkt_data", &STANDARD.encode(bundle))
+            .unwrap();
+    }
+}
+
+pub fn get_inventory() -> Option<MktInventory> {
+    get_json("mkt_inventory").and_then(|json| MktInventory::from_json(&json).ok())
+}
+
+// the inventory at the last export or import, the common base of the next import
+pub fn get_sync_base() -> Option<MktInventory> {
+    get_json("mkt_inventory_base").and_then(|json| MktInventory::from_json(&json).ok())
+}
+
+// without it, the next import is merged without a base
+pub fn set_sync_base(inv: &MktInventory) {
+    if let Err(e) = LocalStorage::set("mkt_inventory_base", inv) {
+        gloo::console::error!(format!("sync base not saved: {}", e));
+    }
+}
+
+pub fn get_history() -> Option<MktInventoryHistory> {
+    get_json("mkt_inventory_history").and_then(|json| MktInventoryHistory::from_json(&json).ok())
+}
+
+pub fn get_hashes() -> Option<MktItemHashes> {
+    get_json("mkt_hash").and_then(|json| MktItemHashes::from_json(&json).ok())
+}
@@ -0,0 +1,144 @@
+use std::{error::Error, fmt::Display, io};
+
+#[derive(Debug)]
+pub enum UpdateError {
+    // the page can't be downloaded
+    Http {
+        url: String,
+        source: reqwest::Error,
+    },
+    // the saved copy of the page can't be read or written
+    Io {
+        url: String,
+        path: String,
+        source: io::Error,
+    },
+    // the page layout changed, an element the scraper needs is gone
+    MissingElement {
+        url: String,
+        selector: String,
+        // index of the table row, when inside a table
+        row: Option<usize>,
+    },
+    // a csv file is malformed, at that line and record when known
+    Csv {
+        path: String,
+        line: Option<u64>,
+        record: Option<u64>,
+        source: csv::Error,
+    },
+}
+
+impl UpdateError {
+    pub fn url(&self) -> &str {
+        match self {
+            UpdateError::Http { url, .. }
+            | UpdateError::Io { url, .. }
+            | UpdateError::MissingElement { url, .. } => url,
+            UpdateError::Csv { path, .. } => path,
+        }
+    }
+
+    pub fn csv(path: &str, source: csv::Error) -> Self {
+        let position = source.position();
+        UpdateError::Csv {
+            path: path.into(),
+            line: position.map(|p| p.line()),
+            record: position.map(|p| p.record()),
+            source,
+        }
+    }
+
+    pub(crate) fn missing(url: &str, selector: &str, row: Option<usize>) -> Self {
+        UpdateError::MissingElement {
+            url: url.into(),
+            selector: selector.into(),
+            row,
+        }
+    }
+}
+
+impl Display for UpdateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UpdateError::Http { url, source } => write!(f, "{}: {}", url, source),
+            UpdateError::Io { url, path, source } => write!(f, "{} ({}): {}", url, path, source),
+            UpdateError::MissingElement { url, selector, row } => {
+                write!(f, "{}: no element for {:?}", url, selector)?;
+                if let Some(row) = row {
+                    write!(f, " in row {}", row)?;
+                }
+                Ok(())
+            }
+            UpdateError::Csv {
+                path,
+                line,
+                record,
+                source,
+            } => {
+                write!(f, "{}", path)?;
+                if let (Some(line), Some(record)) = (line, record) {
+                    write!(f, " (line {}, record {})", line, record)?;
+                }
+                write!(f, ": {}", source)
+            }
+        }
+    }
+}
+
+impl Error for UpdateError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            UpdateError::Http { source, .. } => Some(source),
+            UpdateError::Io { source, .. } => Some(source),
+            UpdateError::Csv { source, .. } => Some(source),
+            UpdateError::MissingElement { .. } => None,
+        }
+    }
+}
+
+// a row skipped by a scraper, the rest of the page is still used
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpdateWarning {
+    pub url: String,
+    pub row: usize,
+    pub message: String,
+}
+
+impl Display for UpdateWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: row {}: {}", self.url, self.row, self.message)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct UpdateReport {
+    pub warnings: Vec<UpdateWarning>,
+}
+
+impl UpdateReport {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.warnings.is_empty()
+    }
+
+    pub fn warn(&mut self, url: &str, row: usize, message: impl Into<String>) {
+        self.warnings.push(UpdateWarning {
+            url: url.into(),
+            row,
+            message: message.into(),
+        });
+    }
+}
+
+impl Display for UpdateReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for w in &self.warnings {
+            writeln!(f, "WARNING: {}", w)?;
+        }
+        Ok(())
+    }
+}
@@ -1,37 +1,58 @@
 #![feature(try_blocks)]
 #![allow(dead_code)]
 
-use std::convert::TryInto;
+use std::{
+    convert::{TryFrom, TryInto},
+    ops::Range,
+};
 
+use chrono::{Datelike, NaiveDate};
 use itertools::Itertools;
-use lazy_static::lazy_static;
-use mkt_data::*;
+use mkt_data::{rules::MII_LADDER, *};
 use regex::Regex;
-use reqwest::blocking::{Client, ClientBuilder};
 use scraper::{Element, ElementRef, Html, Selector};
 
-lazy_static! {
-    static ref HTTP_CLIENT: Client = ClientBuilder::new().cookie_store(true).build().unwrap();
-}
+mod error;
+mod source;
+
+pub use error::*;
+pub use source::*;
 
-pub fn update_mkt_item_data(data: &mut MktData, i_type: ItemType) {
+pub fn update_mkt_item_data(
+    source: &dyn PageSource,
+    data: &mut MktData,
+    i_type: ItemType,
+    report: &mut UpdateReport,
+) -> Result<(), UpdateError> {
     // get data (from Super Mario Wiki)
     let url = match i_type {
         ItemType::Driver => "https://www.mariowiki.com/List_of_drivers_in_Mario_Kart_Tour",
         ItemType::Kart => "https://www.mariowiki.com/List_of_karts_in_Mario_Kart_Tour",
         ItemType::Glider => "https://www.mariowiki.com/List_of_gliders_in_Mario_Kart_Tour",
     };
-    match i_type {
-        // the page format changed for drivers, might change as well for karts and gliders
-        ItemType::Driver => parse_items_new_format(url, data, i_type, 7),
-        ItemType::Kart => parse_items_new_format(url, data, i_type, 5),
-        ItemType::Glider => parse_items_new_format(url, data, i_type, 5),
-    }
+    let content = source.fetch(url)?;
+    let document = Html::parse_document(&content);
+
+    // the page format changed for drivers, might change as well for karts and gliders
+    let row_num = match i_type {
+        ItemType::Driver => 7,
+        ItemType::Kart => 5,
+        ItemType::Glider => 5,
+    };
+    parse_items_new_format(&document, url, data, i_type, row_num, report)?;
+    // the skills are on the same page
+    parse_item_skills(&document, data, i_type, row_num);
+    Ok(())
 }
 
-fn parse_items(url: &str, data: &mut MktData, i_type: ItemType) {
-    let resp = HTTP_CLIENT.get(url).send().unwrap();
-    let content = resp.text().unwrap();
+fn parse_items(
+    source: &dyn PageSource,
+    url: &str,
+    data: &mut MktData,
+    i_type: ItemType,
+    report: &mut UpdateReport,
+) -> Result<(), UpdateError> {
+    let content = source.fetch(url)?;
 
     let document = Html::parse_document(&content);
 
@@ -41,8 +62,8 @@ fn parse_items(url: &str, data: &mut MktData, i_type: ItemType) {
     let rarity_select = Selector::parse("td:nth-of-type(3)").unwrap();
 
     let mut i = 1;
-    for item in document.select(&items_select) {
-        let _: Option<_> = try {
+    for (row, item) in document.select(&items_select).enumerate() {
+        let added: Option<_> = try {
             let name = item
                 .select(&name_select)
                 .next()?
@@ -70,40 +91,57 @@ fn parse_items(url: &str, data: &mut MktData, i_type: ItemType) {
             .insert(item.id.clone(), item);
             i += 1;
         };
+        if added.is_none() {
+            report.warn(url, row, "no name, image or rarity");
+        }
     }
+    Ok(())
 }
 
-fn parse_items_new_format(url: &str, data: &mut MktData, i_type: ItemType, row_num: usize) {
+fn parse_items_new_format(
+    document: &Html,
+    url: &str,
+    data: &mut MktData,
+    i_type: ItemType,
+    row_num: usize,
+    report: &mut UpdateReport,
+) -> Result<(), UpdateError> {
     let name_rgx = Regex::new("<br/?>").unwrap();
 
-    let resp = HTTP_CLIENT.get(url).send().unwrap();
-    let content = resp.text().unwrap();
-
-    let document = Html::parse_document(&content);
-
     let table_select = Selector::parse("h2 + table tbody").unwrap();
     let row_select = Selector::parse("tr").unwrap();
     let cell_select = Selector::parse("th a[title]:first-child, td").unwrap();
 
-    let table = document.select(&table_select).next().unwrap();
+    let table = document
+        .select(&table_select)
+        .next()
+        .ok_or_else(|| UpdateError::missing(url, "h2 + table tbody", None))?;
     let rows = table.select(&row_select);
 
     let mut i = 1;
-    for mut rs in rows
+    for (c, mut rs) in rows
         .map(|r| r.select(&cell_select))
         .chunks(row_num)
         .into_iter()
+        .enumerate()
     {
-        let (names, _, _, rarities) = rs.next_tuple().unwrap();
+        let row = c * row_num;
+        let (names, _, _, rarities) = match rs.next_tuple() {
+            Some(r) => r,
+            None => {
+                report.warn(url, row, "incomplete item rows");
+                continue;
+            }
+        };
         for (name, rarity) in names.zip(rarities) {
             let name = Some(name)
                 .into_iter()
                 .chain(name.next_siblings().filter_map(ElementRef::wrap))
                 .map(|n| n.inner_html())
                 .join(" ");
-            let _: Option<_> = try {
-                let name = name_rgx.replace_all(&name, " ").trim().into();
-                let rarity = rarity.text().next()?.trim().try_into().ok()?;
+            let name: String = name_rgx.replace_all(&name, " ").trim().into();
+            let rarity: Option<Rarity> = try { rarity.text().next()?.trim().try_into().ok()? };
+            if let Some(rarity) = rarity {
                 let item = Item::new(i_type, rarity, name, Some(i));
 
                 // println!("{:?}", item);
@@ -114,12 +152,97 @@ fn parse_items_new_format(url: &str, data: &mut MktData, i_type: ItemType, row_n
                 }
                 .insert(item.id.clone(), item);
                 i += 1;
-            };
+            } else {
+                report.warn(url, row + 3, format!("no rarity for {}", name));
+            }
         }
     }
+    Ok(())
+}
+
+// only the skills, for items from the template which has none
+pub fn update_mkt_item_skill_data(
+    source: &dyn PageSource,
+    data: &mut MktData,
+    i_type: ItemType,
+) -> Result<(), UpdateError> {
+    // get data (from Super Mario Wiki)
+    let url = match i_type {
+        ItemType::Driver => "https://www.mariowiki.com/List_of_drivers_in_Mario_Kart_Tour",
+        ItemType::Kart => "https://www.mariowiki.com/List_of_karts_in_Mario_Kart_Tour",
+        ItemType::Glider => "https://www.mariowiki.com/List_of_gliders_in_Mario_Kart_Tour",
+    };
+    let content = source.fetch(url)?;
+    let document = Html::parse_document(&content);
+
+    match i_type {
+        ItemType::Driver => parse_item_skills(&document, data, i_type, 7),
+        ItemType::Kart => parse_item_skills(&document, data, i_type, 5),
+        ItemType::Glider => parse_item_skills(&document, data, i_type, 5),
+    }
+    Ok(())
 }
 
-pub fn update_mkt_item_coverage_data(data: &mut MktData) {
+// same layout as parse_items_new_format, the skill is the third row of each chunk,
+// skills list their value for each level: "Rocket Start Plus (+5/+10/+15)"
+pub fn parse_item_skills(document: &Html, data: &mut MktData, i_type: ItemType, row_num: usize) {
+    let name_rgx = Regex::new("<br/?>").unwrap();
+    let value_rgx = Regex::new(r"\d+").unwrap();
+
+    let table_select = Selector::parse("h2 + table tbody").unwrap();
+    let row_select = Selector::parse("tr").unwrap();
+    let cell_select = Selector::parse("th a[title]:first-child, td").unwrap();
+
+    let table = match document.select(&table_select).next() {
+        Some(table) => table,
+        None => return,
+    };
+    let rows = table.select(&row_select);
+    let item_names = data.item_name_index();
+
+    for mut rs in rows
+        .map(|r| r.select(&cell_select))
+        .chunks(row_num)
+        .into_iter()
+    {
+        let (names, _, skills) = match rs.next_tuple() {
+            Some(r) => r,
+            None => continue,
+        };
+        for (name, skill) in names.zip(skills) {
+            let name = Some(name)
+                .into_iter()
+                .chain(name.next_siblings().filter_map(ElementRef::wrap))
+                .map(|n| n.inner_html())
+                .join(" ");
+            let name = name_rgx.replace_all(&name, " ");
+            let text = skill.text().collect::<String>();
+            let (skill_name, values) = text.split_once('(').unwrap_or((&text, ""));
+            if skill_name.trim().is_empty() {
+                continue;
+            }
+            let mut skill = Skill::new(skill_name.trim().into());
+            skill.values = value_rgx
+                .find_iter(values)
+                .filter_map(|v| v.as_str().parse().ok())
+                .collect();
+
+            let id = item_names
+                .get(&name, Some(i_type))
+                .cloned()
+                .unwrap_or_else(|| item_id_from_name(name.trim(), i_type));
+            if let Some(item) = data.get_item_mut(&id) {
+                item.skill = Some(skill);
+            }
+        }
+    }
+}
+
+pub fn update_mkt_item_coverage_data(
+    source: &dyn PageSource,
+    data: &mut MktData,
+    report: &mut UpdateReport,
+) -> Result<(), UpdateError> {
     let name_rgx = Regex::new("('s icon)? from.*").unwrap();
 
     let urls = [
@@ -134,10 +257,17 @@ pub fn update_mkt_item_coverage_data(data: &mut MktData) {
         ("3DS", "https://www.mariowiki.com/List_of_favored_and_favorite_Mario_Kart_7_(3DS)_courses_in_Mario_Kart_Tour"),
     ];
 
+    // courses missing from the course list go after the others
+    let mut sort = data
+        .courses
+        .values()
+        .filter_map(|c| c.sort)
+        .max()
+        .unwrap_or(0);
+
     for (prefix, url) in urls {
         // get data (from Super Mario Wiki)
-        let resp = HTTP_CLIENT.get(url).send().unwrap();
-        let content = resp.text().unwrap();
+        let content = source.fetch(url)?;
 
         let document = Html::parse_document(&content);
         let courses_select = Selector::parse("h2 + table").unwrap();
@@ -146,9 +276,12 @@ pub fn update_mkt_item_coverage_data(data: &mut MktData) {
         let item_select = Selector::parse("a").unwrap();
         let course_name_select = Selector::parse("a[title]").unwrap();
 
-        let mut i = 1;
+        let mut row = 0;
         for course in document.select(&courses_select) {
+            // rows are counted over all the tables of the page
+            row += 1;
             for (high_ends, supers, normals) in course.select(&row_select).skip(1).tuples() {
+                row += 3;
                 let i_types = Some(None).into_iter().chain(
                     IntoIterator::into_iter([ItemType::Driver, ItemType::Kart, ItemType::Glider])
                         .map(Option::Some)
@@ -251,17 +384,21 @@ pub fn update_mkt_item_coverage_data(data: &mut MktData) {
                 // println!("{:?}", &gliders);
 
                 if let Some(course) = course {
-                    let course_id = course_id_from_name(&course);
-                    data.courses
-                        .insert(course_id.clone(), Course::new(course.to_string(), Some(i)));
-                    let course = data.courses.get_mut(&course_id).unwrap();
-                    i += 1;
+                    // the course list is kept, the pages may name a course differently
+                    let course_id = data
+                        .find_course_by_name(&course)
+                        .map(|c| c.id.clone())
+                        .unwrap_or_else(|| course_id_from_name(&course));
+                    let course = data.courses.entry(course_id.clone()).or_insert_with(|| {
+                        sort += 1;
+                        Course::new(course, Some(sort))
+                    });
 
                     // drivers
                     let mut drivers_id: Vec<ItemRequirement> = vec![];
                     for (driver, lvl) in drivers {
                         let driver_id = driver_id_from_name(&driver);
-                        let _: Option<_> = try {
+                        let found: Option<_> = try {
                             let driver = data.drivers.get_mut(&driver_id)?;
                             if lvl > 0 {
                                 driver
@@ -274,6 +411,13 @@ pub fn update_mkt_item_coverage_data(data: &mut MktData) {
                             }
                             drivers_id.push((driver_id, lvl).into());
                         };
+                        if found.is_none() {
+                            report.warn(
+                                url,
+                                row,
+                                format!("unknown driver {} on {}", driver, course_id),
+                            );
+                        }
                     }
                     course
                         .favorite_items
@@ -286,7 +430,7 @@ pub fn update_mkt_item_coverage_data(data: &mut MktData) {
                     let mut karts_id: Vec<ItemRequirement> = vec![];
                     for (kart, lvl) in karts {
                         let kart_id = kart_id_from_name(&kart);
-                        let _: Option<_> = try {
+                        let found: Option<_> = try {
                             let kart = data.karts.get_mut(&kart_id)?;
                             if lvl > 0 {
                                 kart.favorite_courses
@@ -296,6 +440,13 @@ pub fn update_mkt_item_coverage_data(data: &mut MktData) {
                             }
                             karts_id.push((kart_id, lvl).into());
                         };
+                        if found.is_none() {
+                            report.warn(
+                                url,
+                                row,
+                                format!("unknown kart {} on {}", kart, course_id),
+                            );
+                        }
                     }
                     course
                         .favorite_items
@@ -308,7 +459,7 @@ pub fn update_mkt_item_coverage_data(data: &mut MktData) {
                     let mut gliders_id: Vec<ItemRequirement> = vec![];
                     for (glider, lvl) in gliders {
                         let glider_id = glider_id_from_name(&glider);
-                        let _: Option<_> = try {
+                        let found: Option<_> = try {
                             let glider = data.gliders.get_mut(&glider_id)?;
                             if lvl > 0 {
                                 glider
@@ -321,6 +472,13 @@ pub fn update_mkt_item_coverage_data(data: &mut MktData) {
                             }
                             gliders_id.push((glider_id, lvl).into());
                         };
+                        if found.is_none() {
+                            report.warn(
+                                url,
+                                row,
+                                format!("unknown glider {} on {}", glider, course_id),
+                            );
+                        }
                     }
                     course
                         .favorite_items
@@ -328,31 +486,38 @@ pub fn update_mkt_item_coverage_data(data: &mut MktData) {
                     course
                         .favored_items
                         .extend(gliders_id.iter().filter(|r| r.lvl == 0).cloned());
+                } else {
+                    report.warn(url, row, "no course name");
                 }
             }
         }
     }
+    Ok(())
 }
 
-pub fn update_mkt_item_and_course_data(data: &mut MktData) {
+pub fn update_mkt_item_and_course_data(
+    source: &dyn PageSource,
+    data: &mut MktData,
+) -> Result<(), UpdateError> {
     // get data (from Super Mario Wiki)
-    let resp = HTTP_CLIENT
-        .get("https://www.mariowiki.com/Template:MKT")
-        .send()
-        .unwrap();
-    let content = resp.text().unwrap();
+    let url = "https://www.mariowiki.com/Template:MKT";
+    let content = source.fetch(url)?;
 
     let document = Html::parse_document(&content);
     let rows_select = Selector::parse("tr").unwrap();
     let item_name_select = Selector::parse("td a").unwrap();
 
     let rows = document.select(&rows_select).collect_vec();
+    let rows = |range: Range<usize>| {
+        rows.get(range.clone())
+            .ok_or_else(|| UpdateError::missing(url, "tr", Some(range.end - 1)))
+    };
 
     let rarities = [Rarity::Normal, Rarity::Super, Rarity::HighEnd];
 
     // drivers
     let mut i = 0;
-    for (drivers, rarity) in rows[2..5].iter().zip(rarities.iter()) {
+    for (drivers, rarity) in rows(2..5)?.iter().zip(rarities.iter()) {
         for driver in drivers.select(&item_name_select) {
             let name = driver.text().collect::<String>();
             if name.contains("Mii") {
@@ -367,7 +532,7 @@ pub fn update_mkt_item_and_course_data(data: &mut MktData) {
 
     // karts
     let mut i = 0;
-    for (karts, rarity) in rows[6..9].iter().zip(rarities.iter()) {
+    for (karts, rarity) in rows(6..9)?.iter().zip(rarities.iter()) {
         for kart in karts.select(&item_name_select) {
             i += 1;
             let name = kart.text().collect::<String>();
@@ -378,7 +543,7 @@ pub fn update_mkt_item_and_course_data(data: &mut MktData) {
 
     // gliders
     let mut i = 0;
-    for (gliders, rarity) in rows[9..12].iter().zip(rarities.iter()) {
+    for (gliders, rarity) in rows(9..12)?.iter().zip(rarities.iter()) {
         for glider in gliders.select(&item_name_select) {
             i += 1;
             let name = glider.text().collect::<String>();
@@ -389,7 +554,7 @@ pub fn update_mkt_item_and_course_data(data: &mut MktData) {
 
     // courses
     let mut i = 0;
-    for (courses, prefix) in rows[14..26].iter().zip([
+    for (courses, prefix) in rows(14..26)?.iter().zip([
         "", "", "", "SNES", "N64", "GBA", "GCN", "DS", "Wii", "3DS", "", "",
     ]) {
         for course in courses.select(&item_name_select) {
@@ -430,15 +595,17 @@ pub fn update_mkt_item_and_course_data(data: &mut MktData) {
             }
         }
     }
+    Ok(())
 }
 
-pub fn update_mkt_mii_data(data: &mut MktData) {
+pub fn update_mkt_mii_data(
+    source: &dyn PageSource,
+    data: &mut MktData,
+    report: &mut UpdateReport,
+) -> Result<(), UpdateError> {
     // get data (from Super Mario Wiki)
-    let resp = HTTP_CLIENT
-        .get("https://www.mariowiki.com/Mii")
-        .send()
-        .unwrap();
-    let content = resp.text().unwrap();
+    let url = "https://www.mariowiki.com/Mii";
+    let content = source.fetch(url)?;
 
     let document = Html::parse_document(&content);
     let span_select = Selector::parse("#Mario_Kart_Tour").unwrap();
@@ -448,24 +615,171 @@ pub fn update_mkt_mii_data(data: &mut MktData) {
     let table = document
         .select(&span_select) // span
         .next()
-        .unwrap()
-        .parent() // h4
-        .unwrap()
+        .and_then(|e| e.parent()) // h4
+        .ok_or_else(|| UpdateError::missing(url, "#Mario_Kart_Tour", None))?
         .next_siblings()
         .find(|e| e.value().as_element().map(|e| e.name()).unwrap_or_default() == "table")
-        .map(ElementRef::wrap)
-        .unwrap()
-        .unwrap();
+        .and_then(ElementRef::wrap)
+        .ok_or_else(|| UpdateError::missing(url, "#Mario_Kart_Tour ~ table", None))?;
 
     let rows = table.select(&row_select);
 
     let mut i = data.drivers.len() as u32;
-    for row in rows.skip(2).step_by(4) {
+    for (r, row) in rows.enumerate().skip(2).step_by(4) {
         for cell in row.select(&cell_select) {
+            let name = match cell.text().next() {
+                Some(name) => name,
+                None => {
+                    report.warn(url, r, "no Mii name");
+                    continue;
+                }
+            };
             i += 1;
-            let name = cell.text().next().unwrap();
             let item = Item::new(ItemType::Driver, Rarity::HighEnd, name.into(), Some(i));
+            data.rules.items.insert(item.id.clone(), MII_LADDER.into());
             data.drivers.insert(item.id.clone(), item);
         }
     }
+    Ok(())
+}
+
+pub fn update_mkt_tour_data(
+    source: &dyn PageSource,
+    data: &mut MktData,
+) -> Result<(), UpdateError> {
+    // get data (from Super Mario Wiki)
+    let content = source.fetch("https://www.mariowiki.com/List_of_tours_in_Mario_Kart_Tour")?;
+
+    parse_tours(&content, data);
+    Ok(())
+}
+
+// one row per tour: name, dates, then the courses and spotlight items as links
+pub fn parse_tours(content: &str, data: &mut MktData) {
+    let document = Html::parse_document(content);
+    let row_select = Selector::parse("table.wikitable tr").unwrap();
+    let cell_select = Selector::parse("th, td").unwrap();
+    let link_select = Selector::parse("a").unwrap();
+
+    let mut i = data.tours.len() as u32;
+    for row in document.select(&row_select) {
+        let cells = row.select(&cell_select).collect_vec();
+        let tour: Option<_> = try {
+            let name = cells.get(0)?.text().collect::<String>().trim().to_string();
+            let dates = cells.get(1)?.text().collect::<String>();
+            let (start, end) = parse_tour_dates(&dates)?;
+            i += 1;
+            let mut tour = Tour::new(name, start, end, Some(i));
+            for link in cells[2..].iter().flat_map(|c| c.select(&link_select)) {
+                let name = link.text().collect::<String>();
+                if let Some(course) = data.find_course_by_name(&name) {
+                    if !tour.courses.contains(&course.id) {
+                        tour.courses.push(course.id.clone());
+                    }
+                } else if let Some(item) = data.find_item_by_name(&name) {
+                    if !tour.spotlight_items.contains(&item.id) {
+                        tour.spotlight_items.push(item.id.clone());
+                    }
+                }
+            }
+            tour
+        };
+        if let Some(tour) = tour.filter(|t| !t.courses.is_empty()) {
+            data.tours.insert(tour.id.clone(), tour);
+        }
+    }
+}
+
+// "January 25 - February 8, 2022" or "December 27, 2022 - January 10, 2023"
+pub fn parse_tour_dates(text: &str) -> Option<(NaiveDate, NaiveDate)> {
+    let (start, end) = text.split_once(['–', '-'])?;
+    let end = NaiveDate::parse_from_str(end.trim(), "%B %d, %Y").ok()?;
+    let start = start.trim();
+    let start = match NaiveDate::parse_from_str(start, "%B %d, %Y") {
+        Ok(start) => start,
+        Err(_) => {
+            let start =
+                NaiveDate::parse_from_str(&format!("{}, {}", start, end.year()), "%B %d, %Y")
+                    .ok()?;
+            // the tour started the year before
+            if start > end {
+                start.with_year(end.year() - 1)?
+            } else {
+                start
+            }
+        }
+    };
+    Some((start, end))
+}
+
+// Localized names, the wiki only has the english ones. One row per item or course,
+// one column per locale ("en", "fr", ...), matched on the english name. An optional
+// "type" column (driver, kart, glider or course) is used for names shared by several.
+pub fn parse_names(
+    content: &str,
+    source_name: &str,
+    data: &mut MktData,
+    report: &mut UpdateReport,
+) -> Result<(), UpdateError> {
+    let csv_error = |e| UpdateError::csv(source_name, e);
+    let mut rdr = csv::Reader::from_reader(content.as_bytes());
+    let headers = rdr.headers().map_err(csv_error)?.clone();
+    let type_col = headers
+        .iter()
+        .position(|h| h.trim().eq_ignore_ascii_case("type"));
+    let locales = headers
+        .iter()
+        .enumerate()
+        .filter_map(|(c, h)| Locale::try_from(h.trim()).ok().map(|l| (c, l)))
+        .collect_vec();
+    let en_col = locales
+        .iter()
+        .find(|(_, l)| *l == Locale::En)
+        .map(|(c, _)| *c)
+        .ok_or_else(|| UpdateError::missing(source_name, "en", None))?;
+
+    let item_names = data.item_name_index();
+    for (r, record) in rdr.records().enumerate() {
+        // the header is row 1
+        let r = r + 2;
+        let record = record.map_err(csv_error)?;
+        let name = record.get(en_col).unwrap_or_default().trim();
+        if name.is_empty() {
+            continue;
+        }
+        let kind = type_col
+            .and_then(|c| record.get(c))
+            .map(|t| t.trim().to_lowercase())
+            .filter(|t| !t.is_empty());
+        let i_type = kind.as_deref().and_then(|t| ItemType::try_from(t).ok());
+
+        let names = locales
+            .iter()
+            .filter(|(_, l)| *l != Locale::En)
+            .filter_map(|(c, l)| {
+                let n = record.get(*c)?.trim();
+                (!n.is_empty()).then(|| (*l, n.to_string()))
+            })
+            .collect_vec();
+
+        let names_of = if kind.as_deref() == Some("course") {
+            None
+        } else {
+            item_names.get(name, i_type).cloned()
+        };
+        let names_of = match names_of {
+            Some(id) => data.get_item_mut(&id).map(|i| &mut i.names),
+            None if i_type.is_none() => {
+                let id = data.find_course_by_name(name).map(|c| c.id.clone());
+                id.and_then(|id| data.courses.get_mut(&id))
+                    .map(|c| &mut c.names)
+            }
+            None => None,
+        };
+        match names_of {
+            Some(names_of) => names_of.extend(names),
+            None => report.warn(source_name, r, format!("unknown name {}", name)),
+        }
+    }
+    Ok(())
 }
@@ -1,34 +1,186 @@
 use std::fmt::Write;
 use std::{collections::HashMap, fs};
 
+use clap::{Parser, Subcommand, ValueEnum};
 use itertools::Itertools;
-use mkt_data::*;
+use mkt_data::{rules::MII_LADDER, *};
 use mkt_update::*;
 
 use regex::Regex;
 use unidecode::unidecode;
 
+#[derive(Parser)]
+#[command(about = "Updates the MKT data from the Super Mario Wiki")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Data file to update, the bundle for the web app is saved next to it
+    #[arg(long, global = true, default_value = "data/mkt_data.json")]
+    data: String,
+    /// Item hashes file, merged into the data
+    #[arg(long, global = true, default_value = "data/mkt_hash.json")]
+    hashes: String,
+    /// Changelog of the update, in markdown, the data file must already exist
+    #[arg(long, global = true)]
+    changelog: Option<String>,
+    /// Directory of the cached wiki pages
+    #[arg(long, global = true, default_value = "tmp/wiki_cache")]
+    cache: String,
+    /// Reads the wiki pages from saved copies instead, named like the cached pages
+    #[arg(long, global = true)]
+    fixtures: Option<String>,
+    /// Only uses the cached wiki pages
+    #[arg(long, global = true)]
+    offline: bool,
+    /// Only prints the changelog, nothing is saved
+    #[arg(long, global = true)]
+    dry_run: bool,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Drivers, karts and gliders, with their skills
+    Items {
+        #[arg(long, value_enum, default_value_t = ItemSource::Template)]
+        from: ItemSource,
+    },
+    /// Courses, from the wiki template
+    Courses,
+    /// Mii drivers
+    Miis,
+    /// Favorite and favored items, from the wiki course lists
+    CoverageFromWiki,
+    /// Favorite items, from the csv version of the sheet Coverage Lookup
+    CoverageFromCsv {
+        #[arg(long, default_value = "tmp/coverage.csv")]
+        csv: String,
+    },
+    /// Localized names, from a csv with one column per locale
+    Names {
+        #[arg(long, default_value = "data/mkt_names.csv")]
+        csv: String,
+    },
+    /// Coverage tables of the data, in wiki markup
+    WikiExport {
+        /// Wiki markup of the course lists, for the item icons
+        #[arg(long, default_value = "tmp/wiki.txt")]
+        wiki: String,
+        #[arg(long, default_value = "tmp/wiki_new.txt")]
+        output: String,
+    },
+    /// Everything, from scratch
+    All {
+        #[arg(long, value_enum, default_value_t = CoverageSource::Csv)]
+        coverage: CoverageSource,
+        #[arg(long, default_value = "tmp/coverage.csv")]
+        csv: String,
+        /// Also saves the data with only the csv coverage, before the hashes are merged
+        #[arg(long, default_value = "data/mkt_data_b&g.json")]
+        csv_data: String,
+        /// Localized names, like the names command
+        #[arg(long)]
+        names: Option<String>,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ItemSource {
+    // Template:MKT, it has all the items
+    Template,
+    // the driver, kart and glider lists
+    Lists,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum CoverageSource {
+    Csv,
+    Wiki,
+}
+
 fn main() {
     println!("MKT Data Update");
+    let cli = Cli::parse();
+
+    let source: Box<dyn PageSource> = match &cli.fixtures {
+        Some(dir) => Box::new(FixtureSource::new(dir)),
+        None => Box::new(CachedSource::new(&cli.cache, cli.offline)),
+    };
+    let source = source.as_ref();
+    // only a missing file starts from scratch, a bad one would lose what was added by hand
+    let old_data = match MktData::load(&cli.data) {
+        Ok(data) => Some(data),
+        Err(e) if e.is_not_found() => None,
+        Err(e) => panic!("can't read the data: {}", e),
+    };
+    // the changelog is written against the data before the update
+    if let (Some(changelog), None) = (&cli.changelog, &old_data) {
+        panic!(
+            "no data in {} to write the changelog {} against",
+            cli.data, changelog
+        );
+    }
+    // the steps update the current data, all starts over
+    let mut data = old_data.clone().unwrap_or_default();
+    let mut report = UpdateReport::new();
+
+    let result = match &cli.command {
+        Command::Items { from } => update_items(source, &mut data, *from, &mut report),
+        Command::Courses => update_courses(source, &mut data),
+        Command::Miis => update_miis(source, &mut data, &mut report),
+        Command::CoverageFromWiki => {
+            clear_coverage(&mut data);
+            update_mkt_item_coverage_data(source, &mut data, &mut report)
+        }
+        Command::CoverageFromCsv { csv } => coverage_from_csv(&data, csv).map(|new_data| {
+            data = new_data;
+        }),
+        Command::Names { csv } => names_from_csv(&mut data, csv, &mut report),
+        Command::WikiExport { wiki, output } => {
+            wiki_export(&data, wiki, output);
+            println!("Done");
+            return;
+        }
+        Command::All {
+            coverage,
+            csv,
+            csv_data,
+            names,
+        } => update_all(source, *coverage, &mut report).and_then(|new_data| {
+            data = match coverage {
+                CoverageSource::Csv => {
+                    let data = coverage_from_csv(&new_data, csv)?;
+                    if !cli.dry_run {
+                        data.save(csv_data).unwrap();
+                    }
+                    data
+                }
+                CoverageSource::Wiki => new_data,
+            };
+            match names {
+                Some(names) => names_from_csv(&mut data, names, &mut report),
+                None => Ok(()),
+            }
+        }),
+    };
+    result.unwrap_or_else(|e| panic!("update failed: {}", e));
+    print!("{}", report);
 
-    let mut data = update_data();
+    save_data(&cli, data, old_data);
+    println!("Done");
+}
+
+fn save_data(cli: &Cli, mut data: MktData, old_data: Option<MktData>) {
     println!("courses: {}", data.courses.len());
     println!("drivers: {}", data.drivers.len());
     println!("karts: {}", data.karts.len());
     println!("gliders: {}", data.gliders.len());
 
     // don't overwrite with bad data
-    if !data.courses.is_empty()
-        && !data.drivers.is_empty()
-        && !data.karts.is_empty()
-        && !data.gliders.is_empty()
+    if data.courses.is_empty()
+        || data.drivers.is_empty()
+        || data.karts.is_empty()
+        || data.gliders.is_empty()
     {
-        let hash =
-            MktItemHashes::load("data/mkt_hash.json").unwrap_or_else(|_| MktItemHashes::new());
-        data.merge_hashes(&hash);
-
-        data.save("data/mkt_data.json").unwrap();
-    } else {
         panic!(
             "some data are empty. courses: {}, drivers: {}, karts: {}, gliders: {}",
             data.courses.len(),
@@ -38,24 +190,214 @@ fn main() {
         );
     }
 
-    if std::env::var("MKT_B_G_WIKI_TEST").is_ok() {
-        let bg_data = _test_b_and_g_coverage(&data);
+    let hash = MktItemHashes::load(&cli.hashes).unwrap_or_else(|e| {
+        if !e.is_not_found() {
+            println!("ERROR: {}", e);
+        }
+        MktItemHashes::new()
+    });
+    data.merge_hashes(&hash);
 
-        _test_wiki_coverage(&bg_data);
+    if let Some(old_data) = &old_data {
+        keep_manual_data(&mut data, old_data);
     }
 
-    println!("Done");
+    let report = data.validate();
+    if !report.is_ok() {
+        panic!("invalid data:\n{}", report);
+    }
+
+    // what changed since the last update, for the review
+    let changelog = match &old_data {
+        Some(old_data) => old_data.diff(&data).to_markdown(old_data, &data),
+        None => {
+            println!("no previous data, no changelog");
+            String::new()
+        }
+    };
+    if old_data.is_some() && changelog.is_empty() {
+        println!("no changes");
+    } else {
+        print!("{}", changelog);
+    }
+    if cli.dry_run {
+        println!("Dry run, nothing saved");
+        return;
+    }
+
+    if let Some(path) = &cli.changelog {
+        if !changelog.is_empty() {
+            fs::write(path, changelog).unwrap();
+        }
+    }
+    data.save(&cli.data).unwrap();
+    // what the web app downloads, the hashes are merged in it
+    data.save_bundle(&format!("{}.gz", cli.data)).unwrap();
+}
+
+// the wiki doesn't have everything, keep what was added by hand
+fn keep_manual_data(data: &mut MktData, old_data: &MktData) {
+    for course in data.courses.values_mut() {
+        if let Some(old_course) = old_data.courses.get(&course.id) {
+            if course.names.is_empty() {
+                course.names = old_course.names.clone();
+            }
+        }
+    }
+    for item in data
+        .drivers
+        .values_mut()
+        .chain(data.karts.values_mut())
+        .chain(data.gliders.values_mut())
+    {
+        if let Some(old_item) = old_data.get_item(&item.id) {
+            if item.names.is_empty() {
+                item.names = old_item.names.clone();
+            }
+            if item.skill.is_none() {
+                item.skill = old_item.skill.clone();
+            }
+        }
+    }
+    // older tours may not be listed anymore
+    for (id, tour) in &old_data.tours {
+        data.tours.entry(id.clone()).or_insert_with(|| tour.clone());
+    }
+    for (i, tour) in data
+        .tours
+        .values_mut()
+        .sorted_by_key(|t| (t.start, t.id.clone()))
+        .enumerate()
+    {
+        tour.sort = Some(i as u32 + 1);
+    }
+    // ladders may have been rebalanced by hand, the costs and weights are only in the data
+    data.rules.ladders.extend(old_data.rules.ladders.clone());
+    data.rules.costs.extend(old_data.rules.costs.clone());
+    if old_data.rules.score_weights.is_some() {
+        data.rules.score_weights = old_data.rules.score_weights;
+    }
+    for (id, ladder) in &old_data.rules.items {
+        if data.get_item(id).is_some() {
+            data.rules
+                .items
+                .entry(id.clone())
+                .or_insert_with(|| ladder.clone());
+        }
+    }
+    // renamed items need an alias, or saved inventories lose them
+    for (from, to) in &old_data.aliases {
+        data.aliases
+            .entry(from.clone())
+            .or_insert_with(|| to.clone());
+    }
+    for id in old_data
+        .drivers
+        .keys()
+        .chain(old_data.karts.keys())
+        .chain(old_data.gliders.keys())
+        .sorted()
+    {
+        if data.resolve_item_id(id).is_none() {
+            println!("WARNING: item {id} is gone, add an alias if it was renamed");
+        }
+    }
 }
 
-fn update_data() -> MktData {
+fn update_all(
+    source: &dyn PageSource,
+    coverage: CoverageSource,
+    report: &mut UpdateReport,
+) -> Result<MktData, UpdateError> {
     let mut data = MktData::new();
-    // uses b&g coverage, instead of wiki
-    update_mkt_item_and_course_data(&mut data);
-    update_mkt_mii_data(&mut data);
-    _test_b_and_g_coverage(&data)
+    update_mkt_item_and_course_data(source, &mut data)?;
+    update_mkt_mii_data(source, &mut data, report)?;
+    update_mkt_item_skill_data(source, &mut data, ItemType::Driver)?;
+    update_mkt_item_skill_data(source, &mut data, ItemType::Kart)?;
+    update_mkt_item_skill_data(source, &mut data, ItemType::Glider)?;
+    update_mkt_tour_data(source, &mut data)?;
+    if let CoverageSource::Wiki = coverage {
+        update_mkt_item_coverage_data(source, &mut data, report)?;
+    }
+    Ok(data)
 }
 
-fn _test_b_and_g_coverage(data: &MktData) -> MktData {
+fn update_items(
+    source: &dyn PageSource,
+    data: &mut MktData,
+    from: ItemSource,
+    report: &mut UpdateReport,
+) -> Result<(), UpdateError> {
+    let mut items = MktData::new();
+    match from {
+        ItemSource::Template => {
+            update_mkt_item_and_course_data(source, &mut items)?;
+            items.courses.clear();
+            for i_type in [ItemType::Driver, ItemType::Kart, ItemType::Glider] {
+                update_mkt_item_skill_data(source, &mut items, i_type)?;
+            }
+        }
+        // the lists have the skills
+        ItemSource::Lists => {
+            for i_type in [ItemType::Driver, ItemType::Kart, ItemType::Glider] {
+                update_mkt_item_data(source, &mut items, i_type, report)?;
+            }
+        }
+    }
+    // the coverage of the items is kept
+    data.merge(items);
+    Ok(())
+}
+
+fn update_courses(source: &dyn PageSource, data: &mut MktData) -> Result<(), UpdateError> {
+    let mut courses = MktData::new();
+    update_mkt_item_and_course_data(source, &mut courses)?;
+    courses.drivers.clear();
+    courses.karts.clear();
+    courses.gliders.clear();
+    data.merge(courses);
+    Ok(())
+}
+
+fn update_miis(
+    source: &dyn PageSource,
+    data: &mut MktData,
+    report: &mut UpdateReport,
+) -> Result<(), UpdateError> {
+    // the Miis are sorted after the other drivers
+    let mut miis = MktData::new();
+    miis.drivers = data
+        .drivers
+        .iter()
+        .filter(|(id, _)| data.rules.items.get(*id).map(String::as_str) != Some(MII_LADDER))
+        .map(|(id, d)| (id.clone(), d.clone()))
+        .collect();
+    update_mkt_mii_data(source, &mut miis, report)?;
+    let rules = &miis.rules;
+    miis.drivers
+        .retain(|id, _| rules.items.get(id).map(String::as_str) == Some(MII_LADDER));
+    data.merge(miis);
+    Ok(())
+}
+
+// the coverage steps start over
+fn clear_coverage(data: &mut MktData) {
+    for course in data.courses.values_mut() {
+        course.favorite_items.clear();
+        course.favored_items.clear();
+    }
+    for item in data
+        .drivers
+        .values_mut()
+        .chain(data.karts.values_mut())
+        .chain(data.gliders.values_mut())
+    {
+        item.favorite_courses.clear();
+        item.favored_courses.clear();
+    }
+}
+
+fn coverage_from_csv(data: &MktData, csv: &str) -> Result<MktData, UpdateError> {
     let mut data = data.clone();
 
     data.courses
@@ -71,11 +413,22 @@ fn _test_b_and_g_coverage(data: &MktData) -> MktData {
         .values_mut()
         .for_each(|c| c.favorite_courses = Default::default());
 
+    // names in any language
+    let item_names = data.item_name_index();
+    for (name, ids) in item_names.collisions() {
+        println!("WARNING: name {:?} is used by {}", name, ids.join(", "));
+    }
+
     // csv version of the sheet Coverage Lookup
-    let mut rdr = csv::Reader::from_path("tmp/coverage.csv").unwrap();
+    let content = fs::read_to_string(csv).map_err(|source| UpdateError::Io {
+        url: csv.into(),
+        path: csv.into(),
+        source,
+    })?;
+    let mut rdr = csv::Reader::from_reader(content.as_bytes());
 
     for result in rdr.records() {
-        let record = result.unwrap();
+        let record = result.map_err(|e| UpdateError::csv(csv, e))?;
         let item_name = &record[0];
         let course_name_lvl1 = &record[1];
         let course_name_lvl3 = &record[2];
@@ -104,50 +457,66 @@ fn _test_b_and_g_coverage(data: &MktData) -> MktData {
         let item_name = item_name.to_uppercase();
         let course_name = course_name.to_uppercase();
 
-        let mut item = data
-            .drivers
-            .values_mut()
-            .chain(data.karts.values_mut())
-            .chain(data.gliders.values_mut())
-            .find(|i| i.get_bgr_name() == item_name);
+        let item_id = item_names.get(&item_name, None).cloned();
 
         // exact match first
-        let mut course = data
+        let mut course_id = data
             .courses
-            .values_mut()
-            .find(|c| unidecode(&c.name).to_uppercase().replace("R/T", "RT") == course_name);
+            .values()
+            .find(|c| unidecode(&c.name).to_uppercase().replace("R/T", "RT") == course_name)
+            .map(|c| c.id.clone());
         // match without console
-        if course.is_none() {
-            course = data.courses.values_mut().find(|c| {
-                !c.name.starts_with("RMX")
-                    && unidecode(&c.name)
-                        .to_uppercase()
-                        .replace("R/T", "RT")
-                        .ends_with(&course_name)
-            });
+        if course_id.is_none() {
+            course_id = data
+                .courses
+                .values()
+                .find(|c| {
+                    !c.name.starts_with("RMX")
+                        && unidecode(&c.name)
+                            .to_uppercase()
+                            .replace("R/T", "RT")
+                            .ends_with(&course_name)
+                })
+                .map(|c| c.id.clone());
         }
 
-        if let (Some(item), Some(course)) = (item.as_mut(), course.as_mut()) {
-            course.favorite_items.insert((item.id.clone(), lvl).into());
-            item.favorite_courses
-                .insert((course.id.clone(), lvl).into());
-            // println!("{}, {} lvl {}", item.name, course.name, lvl);
+        if let (Some(item_id), Some(course_id)) = (&item_id, &course_id) {
+            if let Some(course) = data.courses.get_mut(course_id) {
+                course.favorite_items.insert((item_id.clone(), lvl).into());
+            }
+            if let Some(item) = data.get_item_mut(item_id) {
+                item.favorite_courses
+                    .insert((course_id.clone(), lvl).into());
+            }
+            // println!("{}, {} lvl {}", item_id, course_id, lvl);
         } else {
-            if item.is_none() {
+            if item_id.is_none() {
                 println!("ERROR: missing item: {item_name:?}");
             }
-            if course.is_none() {
+            if course_id.is_none() {
                 println!("ERROR: missing course: {course_name:?}");
             }
         }
     }
 
-    data.save("data/mkt_data_b&g.json").unwrap();
-    data
+    Ok(data)
+}
+
+fn names_from_csv(
+    data: &mut MktData,
+    csv: &str,
+    report: &mut UpdateReport,
+) -> Result<(), UpdateError> {
+    let content = fs::read_to_string(csv).map_err(|source| UpdateError::Io {
+        url: csv.into(),
+        path: csv.into(),
+        source,
+    })?;
+    parse_names(&content, csv, data, report)
 }
 
-fn _test_wiki_coverage(data: &MktData) {
-    let wiki = fs::read_to_string("tmp/wiki.txt").unwrap();
+fn wiki_export(data: &MktData, wiki: &str, output: &str) {
+    let wiki = fs::read_to_string(wiki).unwrap();
 
     let mut wiki_items = HashMap::new();
 
@@ -256,5 +625,5 @@ fn _test_wiki_coverage(data: &MktData) {
         writeln!(&mut wiki_new, "\n").unwrap();
     }
 
-    fs::write("tmp/wiki_new.txt", wiki_new).unwrap();
+    fs::write(output, wiki_new).unwrap();
 }
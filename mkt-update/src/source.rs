@@ -0,0 +1,200 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use reqwest::{
+    blocking::{Client, ClientBuilder},
+    header::{ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+    StatusCode, Url,
+};
+
+use crate::UpdateError;
+
+lazy_static! {
+    static ref HTTP_CLIENT: Client = ClientBuilder::new().cookie_store(true).build().unwrap();
+}
+
+// where the scrapers get their pages from
+pub trait PageSource {
+    fn fetch(&self, url: &str) -> Result<String, UpdateError>;
+}
+
+// the live wiki
+pub struct HttpSource;
+
+impl PageSource for HttpSource {
+    fn fetch(&self, url: &str) -> Result<String, UpdateError> {
+        HTTP_CLIENT
+            .get(url)
+            .send()
+            .and_then(|resp| resp.error_for_status())
+            .and_then(|resp| resp.text())
+            .map_err(|source| UpdateError::Http {
+                url: url.into(),
+                source,
+            })
+    }
+}
+
+// A copy of the pages fetched from the wiki, only downloaded again when they changed.
+// The cache can also be read as fixtures, to reproduce an update.
+pub struct CachedSource {
+    pub dir: PathBuf,
+    // only the cached pages, no request is sent
+    pub offline: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedPage {
+    pub body: String,
+    pub etag: Option<String>,
+    pub last_modified: Option<String>,
+}
+
+impl CachedSource {
+    pub fn new(dir: impl Into<PathBuf>, offline: bool) -> Self {
+        CachedSource {
+            dir: dir.into(),
+            offline,
+        }
+    }
+
+    // the body, next to its headers
+    fn paths(&self, url: &str) -> (PathBuf, PathBuf) {
+        let body_path = self.dir.join(fixture_name(url));
+        let headers_path = body_path.with_extension("headers");
+        (body_path, headers_path)
+    }
+
+    pub fn get(&self, url: &str) -> Option<CachedPage> {
+        let (body_path, headers_path) = self.paths(url);
+        let body = fs::read_to_string(body_path).ok()?;
+        let headers = fs::read_to_string(headers_path).unwrap_or_default();
+        let header = |name: &str| {
+            headers
+                .lines()
+                .filter_map(|l| l.split_once(": "))
+                .find(|(n, _)| n.eq_ignore_ascii_case(name))
+                .map(|(_, v)| v.to_string())
+        };
+        Some(CachedPage {
+            body,
+            etag: header(ETAG.as_str()),
+            last_modified: header(LAST_MODIFIED.as_str()),
+        })
+    }
+
+    pub fn put(&self, url: &str, page: &CachedPage) -> Result<(), UpdateError> {
+        let (body_path, headers_path) = self.paths(url);
+        let mut headers = format!("url: {}\n", url);
+        if let Some(etag) = &page.etag {
+            headers += &format!("{}: {}\n", ETAG, etag);
+        }
+        if let Some(last_modified) = &page.last_modified {
+            headers += &format!("{}: {}\n", LAST_MODIFIED, last_modified);
+        }
+        write_cache_file(url, &body_path, &page.body)?;
+        write_cache_file(url, &headers_path, &headers)
+    }
+}
+
+impl PageSource for CachedSource {
+    fn fetch(&self, url: &str) -> Result<String, UpdateError> {
+        if self.offline {
+            return FixtureSource::new(&self.dir).fetch(url);
+        }
+
+        let cached = self.get(url);
+        let mut request = HTTP_CLIENT.get(url);
+        if let Some(page) = &cached {
+            if let Some(etag) = &page.etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &page.last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+        let http = |source| UpdateError::Http {
+            url: url.into(),
+            source,
+        };
+
+        let resp = request.send().map_err(http)?;
+        if let (StatusCode::NOT_MODIFIED, Some(page)) = (resp.status(), cached) {
+            return Ok(page.body);
+        }
+        let resp = resp.error_for_status().map_err(http)?;
+        let header = |name| {
+            resp.headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+        let (etag, last_modified) = (header(ETAG), header(LAST_MODIFIED));
+        let page = CachedPage {
+            body: resp.text().map_err(http)?,
+            etag,
+            last_modified,
+        };
+        self.put(url, &page)?;
+        Ok(page.body)
+    }
+}
+
+fn write_cache_file(url: &str, path: &Path, content: &str) -> Result<(), UpdateError> {
+    let io = |source| UpdateError::Io {
+        url: url.into(),
+        path: path.display().to_string(),
+        source,
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(io)?;
+    }
+    fs::write(path, content).map_err(io)
+}
+
+// saved copies of the pages, named with fixture_name
+pub struct FixtureSource {
+    pub dir: PathBuf,
+}
+
+impl FixtureSource {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        FixtureSource { dir: dir.into() }
+    }
+}
+
+impl PageSource for FixtureSource {
+    fn fetch(&self, url: &str) -> Result<String, UpdateError> {
+        let path = self.dir.join(fixture_name(url));
+        fs::read_to_string(&path).map_err(|source| UpdateError::Io {
+            url: url.into(),
+            path: path.display().to_string(),
+            source,
+        })
+    }
+}
+
+// the path and query of the url, with only file name friendly characters
+// https://www.mariowiki.com/Template:MKT -> Template_MKT.html
+// https://www.mariowiki.com/index.php?title=Mii -> index.php_title_Mii.html
+pub fn fixture_name(url: &str) -> String {
+    lazy_static! {
+        static ref RE: Regex = Regex::new("[^A-Za-z0-9_.-]+").unwrap();
+    }
+    let page = match Url::parse(url) {
+        Ok(url) => match url.query() {
+            Some(query) => format!("{}?{}", url.path(), query),
+            None => url.path().to_string(),
+        },
+        Err(_) => url.to_string(),
+    };
+    let page = RE.replace_all(page.trim_matches('/'), "_");
+    if page.is_empty() {
+        return "index.html".into();
+    }
+    format!("{}.html", page)
+}
@@ -0,0 +1,51 @@
+use std::{env, fs, path::PathBuf};
+
+use mkt_update::*;
+
+const URL: &str = "https://www.mariowiki.com/Template:MKT";
+
+// a fresh directory for each test
+fn get_cache_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("mkt-update-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn cache_round_trip() {
+    let dir = get_cache_dir("round-trip");
+    let cache = CachedSource::new(&dir, true);
+    assert_eq!(cache.get(URL), None);
+
+    let page = CachedPage {
+        body: "<html></html>".into(),
+        etag: Some("\"abc\"".into()),
+        last_modified: Some("Wed, 21 Oct 2015 07:28:00 GMT".into()),
+    };
+    cache.put(URL, &page).unwrap();
+    assert_eq!(cache.get(URL), Some(page));
+    // the cache can be used as fixtures
+    assert_eq!(
+        FixtureSource::new(&dir).fetch(URL).unwrap(),
+        "<html></html>"
+    );
+
+    fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn cache_offline() {
+    let dir = get_cache_dir("offline");
+    let cache = CachedSource::new(&dir, true);
+    assert!(matches!(cache.fetch(URL), Err(UpdateError::Io { .. })));
+
+    let page = CachedPage {
+        body: "<html></html>".into(),
+        etag: None,
+        last_modified: None,
+    };
+    cache.put(URL, &page).unwrap();
+    assert_eq!(cache.fetch(URL).unwrap(), "<html></html>");
+
+    fs::remove_dir_all(dir).unwrap();
+}
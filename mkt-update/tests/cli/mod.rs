@@ -0,0 +1,104 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use chrono::{TimeZone, Utc};
+use mkt_data::*;
+use mkt_update::*;
+
+use crate::get_fixtures;
+
+// a fresh directory for each test
+fn get_data_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("mkt-update-cli-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+// a step of the update on the fixtures
+fn update_command(dir: &Path, command: &str) -> Command {
+    let mut cmd = Command::new(env!("CARGO_BIN_EXE_mkt-update"));
+    cmd.arg(command)
+        .arg("--fixtures")
+        .arg(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures"))
+        .arg("--data")
+        .arg(dir.join("mkt_data.json"))
+        .arg("--hashes")
+        .arg(dir.join("mkt_hash.json"))
+        .arg("--changelog")
+        .arg(dir.join("changelog.md"));
+    cmd
+}
+
+fn run_update(dir: &Path, command: &str) {
+    let output = update_command(dir, command).output().unwrap();
+    assert!(
+        output.status.success(),
+        "{} failed:\n{}",
+        command,
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn courses_then_coverage() {
+    let dir = get_data_dir("coverage");
+    let data_file = dir.join("mkt_data.json");
+    let data_file = data_file.to_str().unwrap();
+    let source = get_fixtures();
+    let mut data = MktData::new();
+    update_mkt_item_and_course_data(&source, &mut data).unwrap();
+    update_mkt_mii_data(&source, &mut data, &mut UpdateReport::new()).unwrap();
+    let last_changed = Utc.with_ymd_and_hms(2023, 10, 1, 0, 0, 0).unwrap();
+    for course in data.courses.values_mut() {
+        course.last_changed = Some(last_changed);
+    }
+    data.save(data_file).unwrap();
+
+    // nothing changed, no changelog
+    run_update(&dir, "courses");
+    assert!(!dir.join("changelog.md").exists());
+    let courses = MktData::load(data_file).unwrap().courses;
+
+    run_update(&dir, "coverage-from-wiki");
+    assert!(!fs::read_to_string(dir.join("changelog.md"))
+        .unwrap()
+        .is_empty());
+    let data = MktData::load(data_file).unwrap();
+    // the courses of the template are kept, not added again from the wiki lists
+    assert_eq!(data.courses.len(), courses.len());
+    for (id, course) in &data.courses {
+        assert_eq!(course.sort, courses[id].sort, "{}", id);
+        assert_eq!(course.last_changed, Some(last_changed), "{}", id);
+    }
+    for id in ["c_new_york_minute", "c_snes_mario_circuit_1r"] {
+        assert!(!data.courses[id].favorite_items.is_empty(), "{}", id);
+    }
+}
+
+#[test]
+fn changelog_without_data() {
+    let dir = get_data_dir("no-data");
+    let output = update_command(&dir, "courses").output().unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("to write the changelog"));
+    assert!(!dir.join("mkt_data.json").exists());
+}
+
+#[test]
+fn unreadable_data() {
+    let dir = get_data_dir("bad-data");
+    let data_file = dir.join("mkt_data.json");
+    fs::write(&data_file, r#"{"version":999}"#).unwrap();
+    let output = update_command(&dir, "courses").output().unwrap();
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("can't read the data"));
+    // left as it was
+    assert_eq!(
+        fs::read_to_string(&data_file).unwrap(),
+        r#"{"version":999}"#
+    );
+}
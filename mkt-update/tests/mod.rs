@@ -0,0 +1,11 @@
+use mkt_update::*;
+
+mod cache;
+mod cli;
+mod names;
+mod scrapers;
+
+// saved copies of the wiki pages, trimmed down to a few items and courses
+pub fn get_fixtures() -> FixtureSource {
+    FixtureSource::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures"))
+}
@@ -0,0 +1,77 @@
+use mkt_data::*;
+use mkt_update::*;
+
+use crate::get_fixtures;
+
+const NAMES: &str = "\
+type,en,fr,jp
+driver,Mario,Mario,マリオ
+,Peach Parasol,Ombrelle de Peach,
+course,New York Minute,Minute New York,ニューヨークドリーム
+,Luigi,Luigi,ルイージ
+";
+
+#[test]
+fn names_from_csv() {
+    let mut data = MktData::new();
+    update_mkt_item_and_course_data(&get_fixtures(), &mut data).unwrap();
+    let mut report = UpdateReport::new();
+    parse_names(NAMES, "names.csv", &mut data, &mut report).unwrap();
+
+    assert_eq!(data.drivers["d_mario"].get_name(Locale::Jp), "マリオ");
+    assert_eq!(
+        data.gliders["g_peach_parasol"].get_name(Locale::Fr),
+        "Ombrelle de Peach"
+    );
+    // no name for that locale
+    assert_eq!(
+        data.gliders["g_peach_parasol"].get_name(Locale::Jp),
+        "Peach Parasol"
+    );
+    assert_eq!(
+        data.find_course_by_name("minute new york")
+            .map(|c| c.id.as_str()),
+        Some("c_new_york_minute")
+    );
+    assert_eq!(
+        data.item_name_index().get("Ombrelle de Peach", None),
+        Some(&"g_peach_parasol".to_string())
+    );
+    assert_eq!(
+        report.to_string(),
+        "WARNING: names.csv: row 5: unknown name Luigi\n"
+    );
+}
+
+#[test]
+fn names_without_english() {
+    let mut data = MktData::new();
+    let result = parse_names(
+        "fr,jp\nMario,マリオ\n",
+        "names.csv",
+        &mut data,
+        &mut UpdateReport::new(),
+    );
+    assert!(matches!(result, Err(UpdateError::MissingElement { .. })));
+}
+
+#[test]
+fn names_malformed_row() {
+    let mut data = MktData::new();
+    let result = parse_names(
+        "type,en,fr\ndriver,Mario,Mario\ndriver,Luigi\n",
+        "names.csv",
+        &mut data,
+        &mut UpdateReport::new(),
+    );
+    let error = result.unwrap_err();
+    assert!(matches!(
+        error,
+        UpdateError::Csv {
+            line: Some(3),
+            record: Some(2),
+            ..
+        }
+    ));
+    assert!(error.to_string().starts_with("names.csv (line 3, record 2): "));
+}
@@ -0,0 +1,242 @@
+use std::{collections::HashMap, fs};
+
+use chrono::NaiveDate;
+use itertools::Itertools;
+use mkt_data::*;
+use mkt_update::*;
+
+use crate::get_fixtures;
+
+const GOLDEN: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/scrapers/mkt_data.json");
+
+// same steps as the update, without the b&g coverage and the tours
+fn get_wiki_data(report: &mut UpdateReport) -> MktData {
+    let source = get_fixtures();
+    let mut data = MktData::new();
+    update_mkt_item_and_course_data(&source, &mut data).unwrap();
+    update_mkt_mii_data(&source, &mut data, report).unwrap();
+    for i_type in [ItemType::Driver, ItemType::Kart, ItemType::Glider] {
+        update_mkt_item_skill_data(&source, &mut data, i_type).unwrap();
+    }
+    update_mkt_item_coverage_data(&source, &mut data, report).unwrap();
+    data
+}
+
+// run with MKT_UPDATE_GOLDEN=1 to accept a new output, after checking the diff
+#[test]
+fn wiki_data_golden() {
+    let mut report = UpdateReport::new();
+    let json = get_wiki_data(&mut report).to_json().unwrap();
+    if std::env::var("MKT_UPDATE_GOLDEN").is_ok() {
+        fs::write(GOLDEN, &json).unwrap();
+    }
+    assert_eq!(json, fs::read_to_string(GOLDEN).unwrap());
+}
+
+#[test]
+fn wiki_data_warnings() {
+    let mut report = UpdateReport::new();
+    get_wiki_data(&mut report);
+    assert_eq!(
+        report.to_string(),
+        "WARNING: https://www.mariowiki.com/Mii: row 6: no Mii name\n\
+         WARNING: https://www.mariowiki.com/List_of_favored_and_favorite_new_courses_in_Mario_Kart_Tour: \
+         row 4: unknown driver Luigi on c_new_york_minute\n"
+    );
+}
+
+#[test]
+fn wiki_data_courses() {
+    let data = get_wiki_data(&mut UpdateReport::new());
+    assert_eq!(
+        data.courses
+            .keys()
+            .map(String::as_str)
+            .sorted()
+            .collect_vec(),
+        vec![
+            "c_new_york_minute",
+            "c_new_york_minute_t",
+            "c_snes_mario_circuit_1",
+            "c_snes_mario_circuit_1r",
+        ]
+    );
+    // the stars give the level
+    let course = &data.courses["c_new_york_minute_t"];
+    assert!(course
+        .favorite_items
+        .contains(&("d_dry_bowser".to_string(), 8).into()));
+    assert!(course
+        .favorite_items
+        .contains(&("k_pipe_frame".to_string(), 6).into()));
+    assert!(course
+        .favored_items
+        .contains(&("d_mario".to_string(), 0).into()));
+    assert!(data.drivers["d_peach"]
+        .favorite_courses
+        .contains(&("c_new_york_minute".to_string(), 3).into()));
+}
+
+#[test]
+fn wiki_data_miis() {
+    let data = get_wiki_data(&mut UpdateReport::new());
+    let mii = &data.drivers["d_mii_racing_suit_a"];
+    assert_eq!(mii.rarity, Rarity::HighEnd);
+    assert_eq!(data.rules.ladder_name(mii), rules::MII_LADDER);
+    // the Mii link of the template is not a driver
+    assert!(data.drivers.get("d_mii").is_none());
+}
+
+#[test]
+fn wiki_data_tours() {
+    let mut data = get_wiki_data(&mut UpdateReport::new());
+    update_mkt_tour_data(&get_fixtures(), &mut data).unwrap();
+    let date = |y, m, d| NaiveDate::from_ymd_opt(y, m, d).unwrap();
+
+    // the tour without known courses is skipped
+    assert_eq!(
+        data.tours.keys().map(String::as_str).sorted().collect_vec(),
+        vec!["t_holiday_tour", "t_mario_tour", "t_new_york_tour"]
+    );
+    let tour = &data.tours["t_new_york_tour"];
+    assert_eq!(
+        (tour.start, tour.end),
+        (date(2021, 12, 27), date(2022, 1, 10))
+    );
+    assert_eq!(
+        tour.courses,
+        vec!["c_new_york_minute", "c_new_york_minute_t"]
+    );
+    assert_eq!(tour.spotlight_items, vec!["d_dry_bowser"]);
+
+    let tour = &data.tours["t_mario_tour"];
+    assert_eq!(
+        (tour.start, tour.end),
+        (date(2022, 1, 25), date(2022, 2, 8))
+    );
+    assert_eq!(
+        tour.courses,
+        vec![
+            "c_snes_mario_circuit_1",
+            "c_snes_mario_circuit_1r",
+            "c_new_york_minute"
+        ]
+    );
+    assert_eq!(tour.spotlight_items, vec!["k_pipe_frame"]);
+
+    // the start year is the one before the end
+    let tour = &data.tours["t_holiday_tour"];
+    assert_eq!(
+        (tour.start, tour.end),
+        (date(2022, 12, 27), date(2023, 1, 10))
+    );
+
+    assert_eq!(
+        data.current_tour(date(2022, 2, 1).and_hms_opt(12, 0, 0).unwrap().and_utc())
+            .map(|t| t.id.as_str()),
+        Some("t_mario_tour")
+    );
+    let tour_data = data.for_tour(&data.tours["t_mario_tour"]);
+    assert_eq!(tour_data.courses.len(), 3);
+}
+
+#[test]
+fn item_lists() {
+    let source = get_fixtures();
+    let mut report = UpdateReport::new();
+    let mut data = MktData::new();
+    for i_type in [ItemType::Driver, ItemType::Kart, ItemType::Glider] {
+        update_mkt_item_data(&source, &mut data, i_type, &mut report).unwrap();
+    }
+    fn items(items: &HashMap<ItemId, Item>) -> Vec<(&str, Rarity)> {
+        items
+            .values()
+            .sorted_by_key(|i| i.sort)
+            .map(|i| (i.name.as_str(), i.rarity))
+            .collect()
+    }
+    assert_eq!(
+        items(&data.drivers),
+        vec![
+            ("Mario", Rarity::Normal),
+            ("Peach", Rarity::Super),
+            ("Dry Bowser", Rarity::HighEnd),
+        ]
+    );
+    assert_eq!(
+        items(&data.karts),
+        vec![("Pipe Frame", Rarity::Normal), ("Mach 8", Rarity::Super)]
+    );
+    assert_eq!(data.gliders.len(), 3);
+    // the skills come from the same pages
+    let skill = data.karts["k_pipe_frame"].skill.as_ref().unwrap();
+    assert_eq!(skill.name, "Rocket Start Plus");
+    assert_eq!(skill.values, vec![5, 10, 15]);
+    assert_eq!(
+        data.karts["k_pipe_frame"].valid_skill_levels(),
+        vec![1, 2, 3]
+    );
+    assert_eq!(
+        data.drivers["d_peach"]
+            .skill
+            .as_ref()
+            .map(|s| s.name.as_str()),
+        Some("Heart")
+    );
+    assert_eq!(
+        report.warnings,
+        vec![UpdateWarning {
+            url: "https://www.mariowiki.com/List_of_karts_in_Mario_Kart_Tour".into(),
+            row: 3,
+            message: "no rarity for Cat Cruiser".into(),
+        }]
+    );
+}
+
+#[test]
+fn missing_element() {
+    let source = FixtureSource::new(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/broken"
+    ));
+    let mut data = MktData::new();
+    let error = update_mkt_mii_data(&source, &mut data, &mut UpdateReport::new()).unwrap_err();
+    assert!(matches!(
+        &error,
+        UpdateError::MissingElement { selector, row: None, .. } if selector == "#Mario_Kart_Tour"
+    ));
+    assert_eq!(error.url(), "https://www.mariowiki.com/Mii");
+}
+
+#[test]
+fn missing_fixture() {
+    let source = FixtureSource::new(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/fixtures/broken"
+    ));
+    let mut data = MktData::new();
+    let error = update_mkt_item_and_course_data(&source, &mut data).unwrap_err();
+    assert!(matches!(error, UpdateError::Io { .. }));
+}
+
+#[test]
+fn fixture_names() {
+    assert_eq!(
+        fixture_name("https://www.mariowiki.com/Template:MKT"),
+        "Template_MKT.html"
+    );
+    assert_eq!(
+        fixture_name("https://www.mariowiki.com/List_of_favored_and_favorite_Mario_Kart:_Double_Dash!!_(GCN)_courses_in_Mario_Kart_Tour"),
+        "List_of_favored_and_favorite_Mario_Kart__Double_Dash___GCN__courses_in_Mario_Kart_Tour.html"
+    );
+    // the whole path and the query, pages with the same name don't share a file
+    assert_eq!(
+        fixture_name("https://www.mariowiki.com/wiki/Mii"),
+        "wiki_Mii.html"
+    );
+    assert_eq!(
+        fixture_name("https://www.mariowiki.com/index.php?title=Mii&action=raw"),
+        "index.php_title_Mii_action_raw.html"
+    );
+    assert_eq!(fixture_name("https://www.mariowiki.com/"), "index.html");
+}